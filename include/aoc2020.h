@@ -0,0 +1,43 @@
+/* aoc2020.h - generated by `aoc2020 header`; don't edit by hand. */
+
+#ifndef AOC2020_H
+#define AOC2020_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* the answer is in out_buf */
+#define AOC_OK 0
+/* no solver for that year, day and part */
+#define AOC_UNKNOWN_PUZZLE 1
+/* a NULL pointer, or the input isn't UTF-8 */
+#define AOC_BAD_ARGUMENT 2
+/* the input couldn't be parsed; out_buf has the error */
+#define AOC_PARSE_ERROR 3
+/* the input parsed but has no answer; out_buf has the error */
+#define AOC_NO_ANSWER 4
+/* the solver panicked; out_buf has the panic message */
+#define AOC_PANICKED 5
+/* out_buf is too small; *out_len is the length needed */
+#define AOC_BUFFER_TOO_SMALL 6
+
+/* Solve a day-part of a year's puzzle with its primary solver.  The input is input_len bytes of
+ * UTF-8 (it needn't be NUL-terminated).  On entry *out_len is the size of out_buf; on return it is
+ * the length of the text written to out_buf, not counting the terminating NUL. */
+int32_t aoc_solve(uint32_t year, uint32_t day, uint32_t part,
+                  const uint8_t *input, size_t input_len,
+                  char *out_buf, size_t *out_len);
+
+/* List the registered solvers, one per line as "<year> <day> <part> <name>", primary solver
+ * first for each day-part.  out_buf and out_len work as for aoc_solve. */
+int32_t aoc_list_solvers(char *out_buf, size_t *out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC2020_H */
@@ -0,0 +1,76 @@
+// Print the answer for every day-part, using cached answers where nothing has changed.
+//
+// Only the primary solvers are run.  An answer is taken from the cache when the input and the
+// solver's version are the same as when it was computed; --no-cache computes every answer again
+// (and refreshes the cache with the results).
+
+
+use std::time::Duration;
+
+use crate::answers;
+use crate::cache::{AnswerCache, CACHE_FILE};
+use crate::inputs;
+use crate::registry;
+use crate::rejected::{self, REJECTED_FILE};
+use crate::runner::{self, Verification};
+use crate::utils;
+
+
+/// Print the answers for all the day-parts; returns Ok(false) if any of them failed, are wrong, or
+/// were ruled out by answers rejected before.
+pub fn all(use_cache: bool) -> Result<bool, String> {
+    let expected = answers::read_expected(answers::ANSWERS_FILE)?;
+    let rejected = rejected::read_rejected(REJECTED_FILE)?;
+    let mut cache = AnswerCache::load(CACHE_FILE);
+    let mut ok = true;
+    let mut computed = 0;
+
+    println!("{:<8} {:<12} {:>20} {:>12}  verification", "day-part", "solver", "answer", "time (ms)");
+    for solver in registry::primary_solvers() {
+        let day_part = format!("{}-{}", solver.day, solver.part);
+        let cached = match inputs::read(solver.input) {
+            Ok(input) if use_cache => cache.get(solver, utils::hash_input(&input)).map(|a| a.to_string()),
+            _ => None,
+        };
+        let (answer, time) = match cached {
+            Some(answer) => (answer, "cached".to_string()),
+            None => {
+                computed += 1;
+                let result = runner::run_solver(solver, 1, &expected);
+                let median = result.median();
+                match result.answer {
+                    Ok(answer) => {
+                        if let Some(hash) = result.input_hash {
+                            cache.insert(solver, hash, &answer);
+                        }
+                        (answer, format_ms(median))
+                    },
+                    Err(e) => {
+                        ok = false;
+                        println!("{:<8} {:<12} {:>20} {:>12}  {}", day_part, solver.name, "-", "-", e);
+                        continue;
+                    },
+                }
+            },
+        };
+        let verification = runner::verify(solver.day, solver.part, &answer, &expected);
+        if let Verification::Wrong(_) = verification {
+            ok = false;
+        }
+        println!("{:<8} {:<12} {:>20} {:>12}  {}", day_part, solver.name, answer, time, verification);
+        if let Some(flag) = rejected::flag(solver.day, solver.part, &answer, &rejected) {
+            ok = false;
+            println!("{:<8} !!! {}", "", flag);
+        }
+    }
+
+    if computed > 0 {
+        cache.save()?;
+    }
+    Ok(ok)
+}
+
+
+fn format_ms(d: Duration) -> String {
+    format!("{:.3}", d.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,70 @@
+// The verified answers for the puzzle inputs, one per day-part in ./input/answers.txt:
+//
+//     <day>-<part> <answer>
+//
+// e.g. '1-2 241861950'.  Blank lines and lines starting with '#' are ignored.
+
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::inputs;
+
+
+pub const ANSWERS_FILE: &str = "./input/answers.txt";
+
+
+#[derive(Error, Debug, Clone)]
+pub enum AnswerError {
+    #[error("corrupted answer line: {0}")]
+    DecodeError(String),
+}
+
+
+/// A verified answer for a day-part.
+#[derive(Debug, Clone)]
+pub struct Expected {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+}
+
+
+impl FromStr for Expected {
+    type Err = AnswerError;
+
+    // parses '1-2 241861950' into day 1, part 2, with the answer '241861950'
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        if parts.len() != 2 {
+            return Err(AnswerError::DecodeError(line.to_string()));
+        }
+        let day_part = parts[0].split('-').collect::<Vec<_>>();
+        if day_part.len() != 2 {
+            return Err(AnswerError::DecodeError(line.to_string()));
+        }
+        let day = day_part[0].parse::<u32>().map_err(|_| AnswerError::DecodeError(line.to_string()))?;
+        let part = day_part[1].parse::<u32>().map_err(|_| AnswerError::DecodeError(line.to_string()))?;
+        Ok(Expected {day, part, answer: parts[1].to_string()})
+    }
+}
+
+
+/// Read the verified answers; a missing file just means nothing is verified.
+pub fn read_expected(file_name: &str) -> Result<Vec<Expected>, String> {
+    let data = match inputs::read(file_name) {
+        Ok(data) => data,
+        Err(_) => return Ok(Vec::new()),
+    };
+    data.lines()
+        .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|l| l.parse::<Expected>().map_err(|e| format!("{}: {}", file_name, e)))
+        .collect()
+}
+
+
+/// The verified answer for a day-part, if there is one.
+pub fn expected_for(expected: &[Expected], day: u32, part: u32) -> Option<&Expected> {
+    expected.iter().find(|e| e.day == day && e.part == part)
+}
@@ -0,0 +1,283 @@
+// Run a manifest of jobs and print one table of the results, for scripted (e.g. nightly) runs.
+//
+// Each line of the manifest is a job:
+//
+//     <day>-<part> <input file> [expect=<answer>] [timeout=<time>]
+//
+// e.g. '8-2 ./input/day8.txt expect=1000 timeout=5s'.  The timeout is in seconds, or in ms with an
+// 'ms' suffix.  Blank lines and lines starting with '#' are ignored.  Jobs run in the order they
+// are listed, or several at a time with --jobs N; either way the table is in manifest order.
+//
+// Solvers run in this process, so a job that times out can't be stopped: it is reported as timed
+// out and left to finish (or not) in the background while the other jobs go on, and is stopped
+// when the process exits at the end of the batch.
+//
+// An answer that was rejected before (see rejected.rs) is flagged under its row.  The jobs' inputs
+// needn't be the ones the answers were rejected for, so it doesn't fail the job.
+
+
+use std::fmt;
+use std::panic;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::registry::{self, SolveError};
+use crate::rejected::{self, REJECTED_FILE};
+use crate::runner::{self, RunError, RunResult};
+
+
+#[derive(Error, Debug, Clone)]
+pub enum JobError {
+    #[error("corrupted job line: {0}")]
+    DecodeError(String),
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub line: usize,
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub expect: Option<String>,
+    pub timeout: Option<Duration>,
+}
+
+
+// '5s', '1.5s', '500ms' or just '5' (seconds)
+fn parse_timeout(s: &str) -> Result<Duration, JobError> {
+    let (number, scale) = match s.strip_suffix("ms") {
+        Some(ms) => (ms, 0.001),
+        None => (s.strip_suffix('s').unwrap_or(s), 1.0),
+    };
+    number.parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n > 0.0)
+        .map(|n| Duration::from_secs_f64(n * scale))
+        .ok_or(JobError::DecodeError(format!("bad timeout '{}'", s)))
+}
+
+
+impl FromStr for Job {
+    type Err = JobError;
+
+    // parses '8-2 ./input/day8.txt expect=1000 timeout=5s'; the line number is filled in later.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        if parts.len() < 2 {
+            return Err(JobError::DecodeError(format!("needs a day-part and an input: {}", line)));
+        }
+        let day_part = parts[0].split('-').collect::<Vec<_>>();
+        let (day, part) = match day_part.as_slice() {
+            [day, part] => match (day.parse::<u32>(), part.parse::<u32>()) {
+                (Ok(day), Ok(part)) => (day, part),
+                _ => return Err(JobError::DecodeError(format!("bad day-part '{}'", parts[0]))),
+            },
+            _ => return Err(JobError::DecodeError(format!("bad day-part '{}'", parts[0]))),
+        };
+        let input = parts[1].to_string();
+        let mut job = Job { line: 0, day, part, input, expect: None, timeout: None };
+        for option in &parts[2..] {
+            match option.split_once('=') {
+                Some(("expect", answer)) => job.expect = Some(answer.to_string()),
+                Some(("timeout", time)) => job.timeout = Some(parse_timeout(time)?),
+                _ => return Err(JobError::DecodeError(format!("unknown option '{}'", option))),
+            }
+        }
+        Ok(job)
+    }
+}
+
+
+/// Read the jobs in a manifest; every bad line is reported, not just the first.
+pub fn read_manifest(file_name: &str) -> Result<Vec<Job>, String> {
+    let data = std::fs::read_to_string(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+    let mut jobs = Vec::new();
+    let mut errors = Vec::new();
+    for (n, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.parse::<Job>() {
+            Ok(job) if registry::solvers_for(job.day, job.part).is_empty() => {
+                errors.push(format!("{}:{}: Day {}-{} not defined (yet?)",
+                                    file_name, n + 1, job.day, job.part));
+            },
+            Ok(job) => jobs.push(Job { line: n + 1, ..job }),
+            Err(e) => errors.push(format!("{}:{}: {}", file_name, n + 1, e)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(jobs)
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+
+enum Status {
+    Done(RunResult),
+    TimedOut(Duration),
+}
+
+
+struct JobResult {
+    job: Job,
+    status: Status,
+}
+
+
+impl JobResult {
+    fn passed(&self) -> bool {
+        match &self.status {
+            Status::Done(result) => match (&result.answer, &self.job.expect) {
+                (Ok(answer), Some(expect)) => answer == expect,
+                (Ok(_), None) => true,
+                (Err(_), _) => false,
+            },
+            Status::TimedOut(_) => false,
+        }
+    }
+
+    fn answer(&self) -> &str {
+        match &self.status {
+            Status::Done(RunResult { answer: Ok(answer), .. }) => answer,
+            _ => "-",
+        }
+    }
+
+    fn millis(&self) -> f64 {
+        match &self.status {
+            Status::Done(result) => result.median().as_secs_f64() * 1000.0,
+            Status::TimedOut(timeout) => timeout.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+
+impl fmt::Display for JobResult {
+    // a short status for the table
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match &self.status {
+            Status::TimedOut(_) => "TIMEOUT",
+            Status::Done(result) => match &result.answer {
+                Ok(_) if self.passed() => "ok",
+                Ok(_) => "WRONG",
+                Err(RunError::ReadFailed(..)) => "UNREADABLE",
+                Err(RunError::Failed(SolveError::ParseError(_))) => "PARSE ERROR",
+                Err(RunError::Failed(_)) => "NO ANSWER",
+                Err(RunError::Panicked(_)) => "PANIC",
+            },
+        };
+        write!(f, "{}", status)
+    }
+}
+
+
+// Run one job with the primary solver, giving up on it after its timeout.
+fn run_job(job: Job) -> JobResult {
+    let solver = registry::solvers_for(job.day, job.part)[0];
+    let (sender, receiver) = mpsc::channel();
+    let input = job.input.clone();
+    thread::spawn(move || {
+        let _ = sender.send(runner::run_solver_quietly(solver, &input, 1, &[]));
+    });
+    // the job's thread only goes quiet without sending if it timed out
+    let result = match job.timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).ok(),
+        None => receiver.recv().ok(),
+    };
+    let status = result
+        .map(Status::Done)
+        .unwrap_or_else(|| Status::TimedOut(job.timeout.unwrap_or_default()));
+    JobResult { job, status }
+}
+
+
+// Run the jobs on `workers` threads, returning the results in the order of the jobs.
+fn run_jobs(jobs: Vec<Job>, workers: usize) -> Vec<JobResult> {
+    let count = jobs.len();
+    let jobs = Arc::new(jobs);
+    let next = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = mpsc::channel();
+    for _ in 0..workers.clamp(1, count.max(1)) {
+        let (jobs, next, sender) = (Arc::clone(&jobs), Arc::clone(&next), sender.clone());
+        thread::spawn(move || loop {
+            let i = next.fetch_add(1, Ordering::SeqCst);
+            match jobs.get(i) {
+                Some(job) => {
+                    if sender.send((i, run_job(job.clone()))).is_err() {
+                        return;
+                    }
+                },
+                None => return,
+            }
+        });
+    }
+    drop(sender);
+
+    let mut results = receiver.iter().collect::<Vec<_>>();
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+
+/// Run the jobs in a manifest, `workers` at a time; returns Ok(false) if any of them failed, timed
+/// out or gave an answer other than the one expected.
+pub fn batch(manifest: &str, workers: usize) -> Result<bool, String> {
+    let jobs = read_manifest(manifest)?;
+    if jobs.is_empty() {
+        return Err(format!("{} has no jobs", manifest));
+    }
+    let rejected = rejected::read_rejected(REJECTED_FILE)?;
+    println!("Running {} job(s) from {}, {} at a time", jobs.len(), manifest, workers.max(1));
+
+    // keep the default panic output quiet for the whole batch; panics are in the table.  The
+    // jobs leave the hook alone, and it is only put back if none of them are still running.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let results = run_jobs(jobs, workers);
+    if results.iter().all(|r| matches!(r.status, Status::Done(_))) {
+        panic::set_hook(hook);
+    }
+
+    let width = results.iter().map(|r| r.job.input.len()).max().unwrap_or(0).max(5);
+    println!("{:>4}  {:<8} {:<width$}  {:<11} {:>20} {:>20} {:>12}", "line", "day-part", "input",
+             "status", "answer", "expected", "time (ms)", width = width);
+    for result in &results {
+        println!("{:>4}  {:<8} {:<width$}  {:<11} {:>20} {:>20} {:>12.3}", result.job.line,
+                 format!("{}-{}", result.job.day, result.job.part), result.job.input,
+                 result.to_string(), result.answer(),
+                 result.job.expect.as_deref().unwrap_or("-"), result.millis(), width = width);
+        if let Status::Done(RunResult { answer: Ok(answer), .. }) = &result.status {
+            if let Some(flag) = rejected::flag(result.job.day, result.job.part, answer, &rejected) {
+                println!("{:>4}  !!! {}", "", flag);
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|r| !r.passed()).collect::<Vec<_>>();
+    println!("\n{} of {} job(s) passed", results.len() - failed.len(), results.len());
+    for result in &failed {
+        let reason = match &result.status {
+            Status::TimedOut(timeout) => {
+                format!("still running after {:.3}s", timeout.as_secs_f64())
+            },
+            Status::Done(RunResult { answer: Err(e), .. }) => e.to_string(),
+            Status::Done(_) => {
+                format!("expected {}, got {}", result.job.expect.as_deref().unwrap_or("-"), result.answer())
+            },
+        };
+        println!("  line {} ({}-{} {}): {}", result.job.line, result.job.day, result.job.part,
+                 result.job.input, reason);
+    }
+    Ok(failed.is_empty())
+}
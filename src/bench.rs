@@ -0,0 +1,187 @@
+// Benchmark the solvers and keep a history of the results.
+//
+// Every benchmark run appends one JSON line per day-part to the history file (commit, timestamp,
+// day-part, solver, median and p95).  With --compare, the new results are checked against the
+// previous (or best) recorded run of each day-part and any that have slowed down by more than the
+// threshold are flagged as regressions.
+
+
+use std::fs::OpenOptions;
+use std::io::prelude::*;
+use std::process::Command;
+use std::time::Duration;
+
+use crate::answers;
+use crate::json::{self, Value};
+use crate::registry;
+use crate::runner::{self, RunResult};
+use crate::utils;
+
+
+pub const HISTORY_FILE: &str = "bench-history.jsonl";
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Baseline {
+    Previous,
+    Best,
+}
+
+
+pub struct BenchOptions {
+    pub day_part: Option<(u32, u32)>,
+    pub runs: usize,
+    pub history: String,
+    pub compare: Option<Baseline>,
+    pub threshold: f64,
+}
+
+
+/// One line of the history file.
+#[derive(Debug, Clone)]
+struct Record {
+    day: u32,
+    part: u32,
+    solver: String,
+    median_ns: u64,
+}
+
+
+impl Record {
+    fn from_json(value: &Value) -> Option<Record> {
+        let day_part = value.get("day_part")?.as_str()?;
+        let mut bits = day_part.split('-');
+        let day = bits.next()?.parse::<u32>().ok()?;
+        let part = bits.next()?.parse::<u32>().ok()?;
+        Some(Record {
+            day,
+            part,
+            solver: value.get("solver")?.as_str()?.to_string(),
+            median_ns: value.get("median_ns")?.as_u64()?,
+        })
+    }
+}
+
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+
+fn read_history(file_name: &str) -> Result<Vec<Record>, String> {
+    let data = match std::fs::read_to_string(file_name) {
+        Ok(data) => data,
+        Err(_) => return Ok(Vec::new()),
+    };
+    let mut records = Vec::new();
+    for (n, line) in data.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let value = json::parse(line).map_err(|e| format!("{}:{}: {}", file_name, n + 1, e))?;
+        let record = Record::from_json(&value)
+            .ok_or(format!("{}:{}: not a benchmark record", file_name, n + 1))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+
+fn append_history(file_name: &str, results: &[RunResult]) -> Result<(), String> {
+    let commit = current_commit();
+    let timestamp = utils::now_timestamp();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .map_err(|e| format!("couldn't open {}: {}", file_name, e))?;
+    for result in results.iter().filter(|r| r.answer.is_ok()) {
+        let record = json::object(vec![
+            ("commit", commit.clone().into()),
+            ("timestamp", timestamp.into()),
+            ("day_part", format!("{}-{}", result.day, result.part).into()),
+            ("solver", result.name.into()),
+            ("runs", result.timings.len().into()),
+            ("median_ns", (result.median().as_nanos() as u64).into()),
+            ("p95_ns", (utils::percentile(&result.timings, 95.0).as_nanos() as u64).into()),
+        ]);
+        writeln!(file, "{}", record).map_err(|e| format!("couldn't write {}: {}", file_name, e))?;
+    }
+    Ok(())
+}
+
+
+// the recorded median to compare a result against, if there is one.
+fn baseline_for(history: &[Record], result: &RunResult, baseline: Baseline) -> Option<u64> {
+    let mut matching = history
+        .iter()
+        .filter(|r| r.day == result.day && r.part == result.part && r.solver == result.name)
+        .map(|r| r.median_ns);
+    match baseline {
+        Baseline::Previous => matching.next_back(),
+        Baseline::Best => matching.min(),
+    }
+}
+
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+
+/// Run the benchmarks; returns Ok(false) if --compare found any regressions.
+pub fn bench(options: &BenchOptions) -> Result<bool, String> {
+    let expected = answers::read_expected(answers::ANSWERS_FILE)?;
+    let solvers = registry::primary_solvers()
+        .into_iter()
+        .filter(|s| options.day_part.map(|(d, p)| s.day == d && s.part == p).unwrap_or(true))
+        .collect::<Vec<_>>();
+    if solvers.is_empty() {
+        return Err("no solvers to benchmark".to_string());
+    }
+    // read the history before this run is added to it.
+    let history = read_history(&options.history)?;
+
+    let mut regressions = 0;
+    let mut results = Vec::new();
+    println!("{:<6} {:<14} {:>12} {:>12}  {}", "day", "solver", "median (ms)", "p95 (ms)",
+             if options.compare.is_some() { "change" } else { "" });
+    for solver in solvers {
+        let result = runner::run_solver(solver, options.runs, &expected);
+        if let Err(e) = &result.answer {
+            println!("{:<6} {:<14} ERROR: {}", format!("{}-{}", result.day, result.part), result.name, e);
+            continue;
+        }
+        let median = result.median();
+        let mut change = String::new();
+        if let Some(baseline) = options.compare {
+            change = match baseline_for(&history, &result, baseline) {
+                None => "no history".to_string(),
+                Some(base_ns) => {
+                    let base = base_ns.max(1) as f64;
+                    let pct = (median.as_nanos() as f64 - base) / base * 100.0;
+                    if pct > options.threshold {
+                        regressions += 1;
+                        format!("{:+.1}%  REGRESSION", pct)
+                    } else {
+                        format!("{:+.1}%", pct)
+                    }
+                },
+            };
+        }
+        println!("{:<6} {:<14} {:>12.3} {:>12.3}  {}", format!("{}-{}", result.day, result.part),
+                 result.name, millis(median), millis(utils::percentile(&result.timings, 95.0)), change);
+        results.push(result);
+    }
+
+    append_history(&options.history, &results)?;
+    if options.compare.is_some() {
+        if regressions > 0 {
+            println!("{} day-part(s) regressed by more than {}%", regressions, options.threshold);
+        } else {
+            println!("No regressions of more than {}%", options.threshold);
+        }
+    }
+    Ok(regressions == 0)
+}
@@ -0,0 +1,113 @@
+// A cache of computed answers.
+//
+// An answer is cached against the year, day-part, solver, solver version and a hash of the input
+// it was computed from, so it is only reused when none of those have changed.  Bumping a solver's
+// version in the registry invalidates its cached answers.  The cache is a file of JSON lines; a
+// line that can't be read is dropped rather than treated as an error.
+
+
+use crate::json::{self, object, Value};
+use crate::registry::{self, Solver};
+
+
+pub const CACHE_FILE: &str = "answer-cache.jsonl";
+
+
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    year: u32,
+    day: u32,
+    part: u32,
+    solver: String,
+    version: String,
+    input_hash: String,
+    answer: String,
+}
+
+
+impl Entry {
+    fn from_json(value: &Value) -> Option<Entry> {
+        Some(Entry {
+            year: value.get("year")?.as_u64()? as u32,
+            day: value.get("day")?.as_u64()? as u32,
+            part: value.get("part")?.as_u64()? as u32,
+            solver: value.get("solver")?.as_str()?.to_string(),
+            version: value.get("version")?.as_str()?.to_string(),
+            input_hash: value.get("input_hash")?.as_str()?.to_string(),
+            answer: value.get("answer")?.as_str()?.to_string(),
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        object(vec![
+            ("year", self.year.into()),
+            ("day", self.day.into()),
+            ("part", self.part.into()),
+            ("solver", self.solver.as_str().into()),
+            ("version", self.version.as_str().into()),
+            ("input_hash", self.input_hash.as_str().into()),
+            ("answer", self.answer.as_str().into()),
+        ])
+    }
+
+    // the same solver, regardless of version and input
+    fn is_for(&self, solver: &Solver) -> bool {
+        self.year == registry::YEAR && self.day == solver.day && self.part == solver.part
+            && self.solver == solver.name
+    }
+}
+
+
+pub struct AnswerCache {
+    file_name: String,
+    entries: Vec<Entry>,
+}
+
+
+impl AnswerCache {
+    /// Load the cache; a missing file is an empty cache.
+    pub fn load(file_name: &str) -> AnswerCache {
+        let entries = std::fs::read_to_string(file_name)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| json::parse(line).ok())
+            .filter_map(|value| Entry::from_json(&value))
+            .collect();
+        AnswerCache { file_name: file_name.to_string(), entries }
+    }
+
+    /// The cached answer for this version of the solver on an input with this hash.
+    pub fn get(&self, solver: &Solver, input_hash: u64) -> Option<&str> {
+        let hash = format!("{:016x}", input_hash);
+        self.entries
+            .iter()
+            .find(|e| e.is_for(solver) && e.version == solver.version && e.input_hash == hash)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// Cache an answer, dropping any answer for the same input and any answers from other
+    /// versions of the solver.
+    pub fn insert(&mut self, solver: &Solver, input_hash: u64, answer: &str) {
+        let hash = format!("{:016x}", input_hash);
+        self.entries
+            .retain(|e| !(e.is_for(solver) && (e.version != solver.version || e.input_hash == hash)));
+        self.entries.push(Entry {
+            year: registry::YEAR,
+            day: solver.day,
+            part: solver.part,
+            solver: solver.name.to_string(),
+            version: solver.version.to_string(),
+            input_hash: hash,
+            answer: answer.to_string(),
+        });
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let data = self.entries
+            .iter()
+            .map(|e| format!("{}\n", e.to_json()))
+            .collect::<String>();
+        std::fs::write(&self.file_name, data)
+            .map_err(|e| format!("couldn't write {}: {}", self.file_name, e))
+    }
+}
@@ -0,0 +1,33 @@
+// Validate an input file with just the day's parser.
+//
+// Solvers stop at the first line they can't parse (and some inputs are only partly wrong), so this
+// runs the parser over every line and reports all the bad ones, with line numbers, in one go.
+
+
+use crate::inputs;
+use crate::registry;
+
+
+/// Check the input for a day (by default the input of its part 1 solver); returns Ok(false) if any
+/// line was rejected.
+pub fn check(day: u32, input: Option<&str>) -> Result<bool, String> {
+    let entry = registry::day(day).ok_or(format!("Day {} not defined (yet?)", day))?;
+    let file_name = match input {
+        Some(file_name) => file_name,
+        None => registry::default_input(day)
+            .ok_or(format!("Day {} has no part 1 solver to take the input from", day))?,
+    };
+    let contents = inputs::read(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+
+    let checked = (entry.check)(&contents);
+    if checked.bad.is_empty() {
+        println!("{}: no problems found", file_name);
+        return Ok(true);
+    }
+    println!("{}: {} bad line(s)", file_name, checked.bad.len());
+    for b in &checked.bad {
+        println!("  line {}: {}: '{}'", b.line, b.error, b.text);
+    }
+    Ok(false)
+}
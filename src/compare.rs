@@ -0,0 +1,59 @@
+// Compare the alternative solvers registered for a day-part.
+//
+// Every solver is run against the same input, the answers are checked to see that they agree and
+// the median timings are reported relative to the fastest solver.
+
+
+use std::time::Instant;
+
+use crate::context::Context;
+use crate::inputs;
+use crate::registry;
+use crate::utils;
+
+
+/// Compare all the solvers for day-part; returns Ok(true) if all of them agree on the answer.
+pub fn compare(day: u32, part: u32, runs: usize) -> Result<bool, String> {
+    let solvers = registry::solvers_for(day, part);
+    if solvers.is_empty() {
+        return Err(format!("Day {0}-{1} not defined (yet?)", day, part));
+    }
+    let file_name = solvers[0].input;
+    let input = inputs::read(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+
+    println!("Comparing {} solver(s) for {}-{} on {} ({} run(s) each)",
+             solvers.len(), day, part, file_name, runs);
+    let mut results = Vec::new();
+    for solver in solvers.iter() {
+        let mut timings = Vec::with_capacity(runs);
+        let mut answer = Err(String::new());
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            // progress would only get in the way of the timings
+            answer = (solver.solve)(&input, &Context::quiet()).map(|s| s.answer).map_err(|e| e.to_string());
+            timings.push(start.elapsed());
+        }
+        results.push((solver.name, answer, utils::median(&timings)));
+    }
+
+    let fastest = results.iter().map(|r| r.2).min().unwrap_or_default();
+    for (name, answer, median) in results.iter() {
+        let shown = match answer {
+            Ok(a) => a.clone(),
+            Err(e) => format!("ERROR: {}", e),
+        };
+        let relative = median.as_secs_f64() / fastest.as_secs_f64().max(f64::MIN_POSITIVE);
+        println!("  {:<16} {:>20} {:>12.3}ms  x{:.2}", name, shown, median.as_secs_f64() * 1000.0,
+                 relative);
+    }
+
+    let first = &results[0].1;
+    let agree = first.is_ok() && results.iter().all(|r| r.1 == *first);
+    if agree {
+        println!("All solvers agree.");
+    } else {
+        println!("Solvers DISAGREE!");
+    }
+    Ok(agree)
+}
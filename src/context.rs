@@ -0,0 +1,139 @@
+// What a solver is given to talk to the outside world.
+//
+// Rather than printing, solvers (and the day-by-day walkthroughs run by `aoc2020 <day>-<part>`)
+// write human-readable output to the context's sink with say!(), report answers through
+// answer() (or answer_for_input() for the answer to the puzzle input itself, rather than to an
+// example or a step on the way), and report progress through progress().  The caller decides
+// where the output goes: stdout, a buffer that can be read back afterwards (for tests, reports,
+// or runs in parallel), or nowhere.  A solver's answer and the evidence for it still come back as its Solution.
+
+
+use std::io::{self, Write};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::json::Value;
+use crate::progress::Progress;
+
+
+/// Write a line of output to a Context's sink, like println!.
+#[macro_export]
+macro_rules! say {
+    ($ctx:expr) => {
+        { use std::io::Write; let _ = writeln!($ctx.out()); }
+    };
+    ($ctx:expr, $($arg:tt)*) => {
+        { use std::io::Write; let _ = writeln!($ctx.out(), $($arg)*); }
+    };
+}
+
+
+/// Where a Context's output goes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sink {
+    Stdout,
+    Capture,
+    Discard,
+}
+
+
+pub struct Output {
+    sink: Sink,
+    captured: Vec<u8>,
+}
+
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.sink {
+            Sink::Stdout => io::stdout().write(buf),
+            Sink::Capture => self.captured.write(buf),
+            Sink::Discard => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.sink {
+            Sink::Stdout => io::stdout().flush(),
+            _ => Ok(()),
+        }
+    }
+}
+
+
+pub struct Context {
+    output: Mutex<Output>,
+    // each answer, and whether it is for the puzzle input
+    answers: Mutex<Vec<(String, Value, bool)>>,
+    progress: Progress,
+}
+
+
+impl Context {
+    pub fn new(sink: Sink, progress: Progress) -> Context {
+        Context {
+            output: Mutex::new(Output { sink, captured: Vec::new() }),
+            answers: Mutex::new(Vec::new()),
+            progress,
+        }
+    }
+
+    /// A context that throws away the output and shows no progress.
+    pub fn quiet() -> Context {
+        Context::new(Sink::Discard, Progress::hidden())
+    }
+
+    /// The sink for human-readable output; see say!().
+    pub fn out(&self) -> MutexGuard<'_, Output> {
+        // a panic while writing leaves nothing worse than a partial line, so carry on
+        self.output.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Report a labelled answer (e.g. the answer for the example and then for the real input);
+    /// it is written to the sink as well as kept for answers().
+    pub fn answer<V: Into<Value>>(&self, label: &str, value: V) {
+        self.report(label, value.into(), false);
+    }
+
+    /// Report the labelled answer to the puzzle input, which is the one to submit; see answer().
+    pub fn answer_for_input<V: Into<Value>>(&self, label: &str, value: V) {
+        self.report(label, value.into(), true);
+    }
+
+    fn report(&self, label: &str, value: Value, for_input: bool) {
+        say!(self, "{}: {}", label, value.to_text());
+        self.answers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((label.to_string(), value, for_input));
+    }
+
+    /// The answers reported so far, in the order they were reported.
+    pub fn answers(&self) -> Vec<(String, Value)> {
+        self.answers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(label, value, _)| (label.clone(), value.clone()))
+            .collect()
+    }
+
+    /// The answers reported for the puzzle input, in the order they were reported.
+    pub fn input_answers(&self) -> Vec<(String, Value)> {
+        self.answers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(_, _, for_input)| *for_input)
+            .map(|(label, value, _)| (label.clone(), value.clone()))
+            .collect()
+    }
+
+    /// The output written so far, if the sink is Sink::Capture.
+    pub fn captured(&self) -> String {
+        String::from_utf8_lossy(&self.out().captured).into_owned()
+    }
+}
@@ -1,32 +1,17 @@
-use std::str::FromStr;
-use std::fmt;
-use std::cell::RefCell;
-use std::collections::{
-    HashMap,
-    HashSet,
-};
+use std::collections::HashMap;
 
 
-use thiserror::Error;
-
-use crate::utils;
-
-
-#[derive(Error, Debug, Clone)]
-pub enum DecodeError {
-    #[error("Corrupt questions")]
-    CorruptError(String),
-    #[error("Invalid Opcode")]
-    InvalidOpCode(String),
-    #[error("unknown passport error")]
-    Unknown,
-}
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::registry::{Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 fn count_intervals(numbers: &[u32]) -> HashMap<u32, u32> {
     let mut counts = HashMap::new();
-    for i in 0..numbers.len()-1 {
-        let diff = numbers[i+1] - numbers[i];
+    for pair in numbers.windows(2) {
+        let diff = pair[1] - pair[0];
         *counts.entry(diff).or_insert(0) += 1;
     }
     // add in the final additional 3 count.
@@ -35,22 +20,89 @@ fn count_intervals(numbers: &[u32]) -> HashMap<u32, u32> {
 }
 
 
-pub fn day10_1() {
+// the first gap of more than 3 jolts in a sorted chain (starting at the outlet's 0), if there is
+// one: the adapters can't be chained across it.
+fn find_gap(chain: &[u32]) -> Option<(u32, u32)> {
+    chain.windows(2).find(|pair| pair[1] - pair[0] > 3).map(|pair| (pair[0], pair[1]))
+}
+
+
+fn gap_error((low, high): (u32, u32)) -> SolveError {
+    SolveError::NoAnswer(format!("nothing bridges the {} jolts from {} to {}", high - low, low, high))
+}
+
+
+// only the parser: every line should be a joltage
+pub fn check(input: &str) -> Checked {
+    utils::check_lines::<u32>(input)
+}
+
+
+// the puzzle's adapters all have different joltages, and in order none is more than 3 jolts
+// above the one before (or the outlet's 0); the reason an input of numbers can't be for day 10,
+// if it can't.
+pub fn implausible(input: &str) -> Option<String> {
+    let mut numbers = utils::parse_lines::<u32>(input).ok()?;
+    if !numbers.contains(&0) {
+        numbers.push(0);
+    }
+    numbers.sort_unstable();
+    if let Some(pair) = numbers.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Some(format!("two adapters are rated {} jolts", pair[0]));
+    }
+    find_gap(&numbers).map(|(low, high)| format!("a gap of {} jolts after {}", high - low, low))
+}
+
+
+// the adapters' joltages, in the order they are listed
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    Ok(utils::parse_lines::<u32>(input)?.into())
+}
+
+
+// how many adapters, their range, and the gaps between them once they are in order
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let mut numbers = utils::parse_lines::<u32>(input)?;
+    let mut stats = utils::number_stats(&numbers);
+    numbers.push(0);
+    numbers.sort_unstable();
+    stats.push(("gaps", utils::histogram(numbers.windows(2).map(|pair| pair[1] - pair[0]))));
+    Ok(json::object(stats))
+}
+
+
+pub fn solve_1(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let mut numbers = utils::parse_lines::<u32>(input)?;
+    if !numbers.contains(&0) {
+        numbers.push(0);
+    }
+    numbers.sort_unstable();
+    if let Some(gap) = find_gap(&numbers) {
+        return Err(gap_error(gap));
+    }
+    let counts = count_intervals(numbers.as_slice());
+    let ones = counts.get(&1).unwrap_or(&0);
+    let threes = counts.get(&3).unwrap_or(&0);
+    Ok(Solution::new(ones * threes))
+}
+
+
+pub fn day10_1(ctx: &Context) -> Result<(), InputError> {
     //let numbers_results = utils::read_file::<u32>("./input/day10-test-data.txt");
-    let numbers_results = utils::read_file::<u32>("./input/day10.txt");
-    let mut numbers = numbers_results.iter().map(|v| v.clone().unwrap()).collect::<Vec<_>>();
+    let mut numbers = utils::read_file::<u32>("./input/day10.txt")?;
     // add in a 0 if it doesn't exist.
     if !numbers.contains(&0) {
         numbers.push(0);
     }
-    println!("{:?}", numbers);
+    say!(ctx, "{:?}", numbers);
     numbers.sort();
-    println!("sorted {:?}", numbers);
+    say!(ctx, "sorted {:?}", numbers);
     let counts = count_intervals(numbers.as_slice());
-    println!("counts {:?}", counts);
+    say!(ctx, "counts {:?}", counts);
     let ones = counts.get(&1).unwrap();
     let threes = counts.get(&3).unwrap();
-    println!("result is {}", ones * threes);
+    ctx.answer_for_input("result", ones * threes);
+    Ok(())
 }
 
 
@@ -85,13 +137,26 @@ fn count_paths(adapters: &[u32]) -> i64 {
 }
 
 
-pub fn day10_2() {
+pub fn solve_2(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let mut numbers = utils::parse_lines::<u32>(input)?;
+    numbers.sort_unstable();
+    if numbers.is_empty() {
+        return Err(SolveError::NoAnswer("there are no adapters".to_string()));
+    }
+    if let Some(gap) = find_gap(&[&[0], numbers.as_slice()].concat()) {
+        return Err(gap_error(gap));
+    }
+    Ok(Solution::new(count_paths(&numbers)))
+}
+
+
+pub fn day10_2(ctx: &Context) -> Result<(), InputError> {
     //let numbers_results = utils::read_file::<u32>("./input/day10-test-data.txt");
     //let numbers_results = utils::read_file::<u32>("./input/day10-small-test-data.txt");
-    let numbers_results = utils::read_file::<u32>("./input/day10.txt");
-    let mut numbers = numbers_results.iter().map(|v| v.clone().unwrap()).collect::<Vec<_>>();
-    println!("{:?}", numbers);
+    let mut numbers = utils::read_file::<u32>("./input/day10.txt")?;
+    say!(ctx, "{:?}", numbers);
     numbers.sort();
-    println!("sorted {:?}", numbers);
-    println!("counted paths: {}", count_paths(&numbers));
+    say!(ctx, "sorted {:?}", numbers);
+    ctx.answer_for_input("counted paths", count_paths(&numbers));
+    Ok(())
 }
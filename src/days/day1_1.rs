@@ -1,34 +1,22 @@
 // Day 1 Part 1 - Expense report
 //
-// Specifically, they need you to find the two entries that sum to 2020 and then multiply those two
-// numbers together.
+// The puzzle and its examples are in puzzles/day1.md.
 
-//For example, suppose your expense report contained the following:
 
-//1721
-//979
-//366
-//299
-//675
-//1456
-
-//In this list, the two entries that sum to 2020 are 1721 and 299. Multiplying them together
-//produces 1721 * 299 = 514579, so the correct answer is 514579.
-
-use std::str::FromStr;
-use std::num::ParseIntError;
-
-use crate::utils;
-
-const NUMBERS: [u32; 6] = [1721, 979, 366, 299, 675, 1456];
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::puzzles;
+use crate::registry::{Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 fn find_pair(numbers: &[u32]) -> Result<(u32, u32), String> {
-    for n1 in 0..(numbers.len() - 1) {
-        for n2 in n1..numbers.len() {
+    for n1 in 0..numbers.len().saturating_sub(1) {
+        for n2 in n1 + 1..numbers.len() {
             let v1 = numbers[n1];
             let v2 = numbers[n2];
-            if v1 + v2 == 2020 {
+            if v1.checked_add(v2) == Some(2020) {
                 return Ok((v1, v2));
             }
         }
@@ -37,30 +25,49 @@ fn find_pair(numbers: &[u32]) -> Result<(u32, u32), String> {
 }
 
 
-fn extract_numbers(parsed: &[Result<u32, ParseIntError>]) -> Vec<u32> {
-    parsed
-        .iter()
-        .map(|x| {
-            match *x {
-                Ok(v) => v,
-                Err(_) => 0 as u32,
-            }
-        })
-        .collect()
+// only the parser: every line should be a number
+pub fn check(input: &str) -> Checked {
+    utils::check_lines::<u32>(input)
+}
+
+
+// the expense report, as a list of numbers
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    Ok(utils::parse_lines::<u32>(input)?.into())
 }
 
-pub fn day1_1() {
-    println!("First let's just do the test:");
-    match find_pair(&NUMBERS) {
-        Ok((v1,v2)) => println!("The numbers are {0} * {1} = {2}", v1, v2, v1 * v2),
-        Err(s) => println!("{0}", s),
+
+// how many entries, and their range
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let numbers = utils::parse_lines::<u32>(input)?;
+    Ok(json::object(utils::number_stats(&numbers)))
+}
+
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let numbers = utils::parse_lines::<u32>(input)?;
+    let (v1, v2) = find_pair(&numbers).map_err(SolveError::NoAnswer)?;
+    Ok(Solution::new(v1 * v2).with("entries", vec![v1, v2]))
+}
+
+
+pub fn day1_1(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "First let's just do the test:");
+    let example = utils::parse_lines::<u32>(puzzles::example(1, "report").unwrap()).unwrap();
+    match find_pair(&example) {
+        Ok((v1,v2)) => ctx.answer(&format!("The numbers are {0} * {1}", v1, v2), v1 * v2),
+        Err(s) => say!(ctx, "{0}", s),
     }
 
-    println!("Now let's read the expenses file and then find in tht file:");
-    let parsed_numbers = utils::read_file::<u32>("./input/day1-1.txt");
-    let numbers = extract_numbers(&parsed_numbers);
+    say!(ctx, "Now let's read the expenses file and then find in tht file:");
+    // skip (but mention) any lines that aren't numbers
+    let (numbers, failures) = utils::read_file_lenient::<u32>("./input/day1-1.txt")?;
+    for failure in &failures {
+        say!(ctx, "skipping {}", failure);
+    }
     match find_pair(&numbers) {
-        Ok((v1,v2)) => println!("The numbers are {0} * {1} = {2}", v1, v2, v1 * v2),
-        Err(s) => println!("{0}", s),
+        Ok((v1,v2)) => ctx.answer_for_input(&format!("The numbers are {0} * {1}", v1, v2), v1 * v2),
+        Err(s) => say!(ctx, "{0}", s),
     }
+    Ok(())
 }
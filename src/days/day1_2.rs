@@ -1,38 +1,24 @@
 // Day 1 Part 2 - Expense report
 //
-// Specifically, they need you to find the two entries that sum to 2020 and then multiply those two
-// numbers together.
+// The puzzle and its examples are in puzzles/day1.md.
 
-//For example, suppose your expense report contained the following:
+use std::cmp::Ordering;
 
-//1721
-//979
-//366
-//299
-//675
-//1456
-
-//Using the above example again, the three entries that sum to 2020 are 979, 366, and 675.
-//Multiplying them together produces the answer, 241861950.
-
-//In your expense report, what is the product of the three entries that sum to 2020?
-
-use std::str::FromStr;
-use std::num::ParseIntError;
-
-use crate::utils;
-
-const NUMBERS: [u32; 6] = [1721, 979, 366, 299, 675, 1456];
+use crate::context::Context;
+use crate::puzzles;
+use crate::registry::{SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 fn find_triple(numbers: &[u32]) -> Result<(u32, u32, u32), String> {
-    for n1 in 0..(numbers.len() - 2) {
-        for n2 in n1..(numbers.len() - 1)  {
-            for n3 in n2..numbers.len() {
+    for n1 in 0..numbers.len().saturating_sub(2) {
+        for n2 in n1 + 1..numbers.len().saturating_sub(1) {
+            for n3 in n2 + 1..numbers.len() {
                 let v1 = numbers[n1];
                 let v2 = numbers[n2];
                 let v3 = numbers[n3];
-                if v1 + v2 + v3== 2020 {
+                if v1.checked_add(v2).and_then(|v| v.checked_add(v3)) == Some(2020) {
                     return Ok((v1, v2, v3));
                 }
             }
@@ -42,30 +28,60 @@ fn find_triple(numbers: &[u32]) -> Result<(u32, u32, u32), String> {
 }
 
 
-fn extract_numbers(parsed: &[Result<u32, ParseIntError>]) -> Vec<u32> {
-    parsed
-        .iter()
-        .map(|x| {
-            match *x {
-                Ok(v) => v,
-                Err(_) => 0 as u32,
+// sort the numbers and then, for each number, walk a pair of pointers in from either end of the
+// rest of the list.  This is O(n^2) rather than the O(n^3) of find_triple().
+fn find_triple_sorted(numbers: &[u32]) -> Result<(u32, u32, u32), String> {
+    let mut sorted = numbers.to_vec();
+    sorted.sort_unstable();
+    for n1 in 0..sorted.len() {
+        let mut lower = n1 + 1;
+        let mut upper = sorted.len() - 1;
+        while lower < upper {
+            // summed as u64s, so three big entries can't overflow
+            let sum = [n1, lower, upper].iter().map(|&n| u64::from(sorted[n])).sum::<u64>();
+            match sum.cmp(&2020) {
+                Ordering::Equal => return Ok((sorted[n1], sorted[lower], sorted[upper])),
+                Ordering::Less => lower += 1,
+                Ordering::Greater => upper -= 1,
             }
-        })
-        .collect()
+        }
+    }
+    Err("Couldn't find a triple that adds to 2020".to_string())
 }
 
-pub fn day1_2() {
-    println!("First let's just do the test:");
-    match find_triple(&NUMBERS) {
-        Ok((v1,v2,v3)) => println!("The numbers are {0} * {1} * {2} = {3}", v1, v2, v3, v1 * v2 * v3),
-        Err(s) => println!("{0}", s),
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let numbers = utils::parse_lines::<u32>(input)?;
+    let (v1, v2, v3) = find_triple(&numbers).map_err(SolveError::NoAnswer)?;
+    Ok(Solution::new(v1 * v2 * v3).with("entries", vec![v1, v2, v3]))
+}
+
+
+pub fn solve_two_pointer(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let numbers = utils::parse_lines::<u32>(input)?;
+    let (v1, v2, v3) = find_triple_sorted(&numbers).map_err(SolveError::NoAnswer)?;
+    Ok(Solution::new(v1 * v2 * v3).with("entries", vec![v1, v2, v3]))
+}
+
+
+pub fn day1_2(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "First let's just do the test:");
+    let example = utils::parse_lines::<u32>(puzzles::example(1, "report").unwrap()).unwrap();
+    match find_triple(&example) {
+        Ok((v1,v2,v3)) => ctx.answer(&format!("The numbers are {0} * {1} * {2}", v1, v2, v3), v1 * v2 * v3),
+        Err(s) => say!(ctx, "{0}", s),
     }
 
-    println!("Now let's read the expenses file and then find in tht file:");
-    let parsed_numbers = utils::read_file::<u32>("./input/day1-1.txt");
-    let numbers = extract_numbers(&parsed_numbers);
+    say!(ctx, "Now let's read the expenses file and then find in tht file:");
+    // skip (but mention) any lines that aren't numbers
+    let (numbers, failures) = utils::read_file_lenient::<u32>("./input/day1-1.txt")?;
+    for failure in &failures {
+        say!(ctx, "skipping {}", failure);
+    }
     match find_triple(&numbers) {
-        Ok((v1,v2,v3)) => println!("The numbers are {0} * {1} * {2} = {3}", v1, v2, v3, v1 * v2 * v3),
-        Err(s) => println!("{0}", s),
+        Ok((v1,v2,v3)) => ctx.answer_for_input(
+            &format!("The numbers are {0} * {1} * {2}", v1, v2, v3), v1 * v2 * v3),
+        Err(s) => say!(ctx, "{0}", s),
     }
+    Ok(())
 }
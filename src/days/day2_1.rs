@@ -1,41 +1,27 @@
 // Day 2 Part 1 - Password Philosophy
 //
+// The puzzle and its examples are in puzzles/day2.md.
 
-//1-3 a: abcde
-//1-3 b: cdefg
-//2-9 c: ccccccccc
-
-//Each line gives the password policy and then the password. The password policy indicates the
-//lowest and highest number of times a given letter must appear for the password to be valid. For
-//example, 1-3 a means that the password must contain a at least 1 time and at most 3 times.
-
-//In the above example, 2 passwords are valid. The middle password, cdefg, is not; it contains no
-//instances of b, but needs at least 1. The first and third passwords are valid: they contain one a
-//or nine c, both within the limits of their respective policies.
-
-
+use std::io::BufRead;
 use std::str::FromStr;
 use std::fmt;
 
 use thiserror::Error;
 
-use crate::utils;
-
-
-const PASSWORDS: &str = "1-3 a: abcde\n\
-    1-3 b: cdefg\n\
-    2-9 c: ccccccccc\n\
-    ";
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::puzzles;
+use crate::registry::{Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Error, Debug, Clone)]
 pub enum PasswordError {
-    #[error("corrupted password line")]
+    #[error("corrupted password line: {0}")]
     DecodeError(String),
-    #[error("corrupted password rules")]
+    #[error("corrupted password rules: {0}")]
     RulesError(String),
-    #[error("unknown password error")]
-    Unknown,
 }
 
 
@@ -51,7 +37,7 @@ impl fmt::Display for Rules {
         // Write strictly the first element into the supplied output
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
-        // is very similar to `println!`.
+        // is very similar to `format!`.
         write!(f, "char: {0}, range: {1}-{2}", self.element, self.bounds.0, self.bounds.1)
     }
 }
@@ -62,11 +48,11 @@ impl FromStr for Rules {
 
     // parses a '1-3 c' into a Rules of bounds (1,3) and element 'c'
     fn from_str(rules: &str) -> Result<Self, Self::Err> {
-        let parts = rules.split(" ").collect::<Vec<_>>();
+        let parts = rules.split(' ').collect::<Vec<_>>();
         if parts.len() != 2 {
             return Err(PasswordError::DecodeError(format!("Missing space in passed string: {}", rules)));
         }
-        let bounds = parts[0].split("-").collect::<Vec<_>>();
+        let bounds = parts[0].split('-').collect::<Vec<_>>();
         if bounds.len() != 2 {
             return Err(PasswordError::RulesError(format!("Missing - in passed bounds: {}", parts[0])));
         }
@@ -76,12 +62,12 @@ impl FromStr for Rules {
         let lv = lb?;
         let uv = ub?;
         if lv >= uv {
-            return Err(PasswordError::RulesError(format!("Rules: lower bound can't be higher than upper bound!")));
+            return Err(PasswordError::RulesError("Rules: lower bound can't be higher than upper bound!".to_string()));
         }
         // now extract the character.
         let chars = parts[1].chars().collect::<Vec<_>>();
         if chars.len() != 1 {
-            return Err(PasswordError::DecodeError(format!("element rule must be a single character")));
+            return Err(PasswordError::DecodeError("element rule must be a single character".to_string()));
         }
         Ok(Rules{element: chars[0], bounds: (lv, uv)})
     }
@@ -101,7 +87,7 @@ impl fmt::Display for PasswordRules {
         // Write strictly the first element into the supplied output
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
-        // is very similar to `println!`.
+        // is very similar to `format!`.
         write!(f, "password: {0}, rules: {1}", self.password, self.rules)
     }
 }
@@ -123,51 +109,112 @@ impl FromStr for PasswordRules {
 }
 
 
+impl From<&PasswordRules> for Value {
+    fn from(pr: &PasswordRules) -> Self {
+        json::object(vec![
+            ("password", pr.password.as_str().into()),
+            ("element", pr.rules.element.to_string().into()),
+            ("lower", pr.rules.bounds.0.into()),
+            ("upper", pr.rules.bounds.1.into()),
+        ])
+    }
+}
+
+
 fn validate_password(pr: &PasswordRules) -> bool {
     let count = pr.password
         .chars()
         .filter(|c| *c == pr.rules.element)
         .count();
     let c32 = count as u32;
-    c32 >= pr.rules.bounds.0 && c32 <= pr.rules.bounds.1
+    (pr.rules.bounds.0..=pr.rules.bounds.1).contains(&c32)
+}
+
+
+// only the parser: every line should be a password and its rules
+pub fn check(input: &str) -> Checked {
+    utils::check_lines::<PasswordRules>(input)
+}
+
+
+// the passwords and their rules
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    let passwords = utils::parse_lines::<PasswordRules>(input)?;
+    Ok(passwords.iter().collect::<Vec<_>>().into())
+}
+
+
+// the spread of the rules' bounds and letters, and the passwords' lengths
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let passwords = utils::parse_lines::<PasswordRules>(input)?;
+    let lengths = passwords.iter().map(|pr| pr.password.len()).collect::<Vec<_>>();
+    Ok(json::object(vec![
+        ("passwords", passwords.len().into()),
+        ("lower bounds", utils::histogram(passwords.iter().map(|pr| pr.rules.bounds.0))),
+        ("upper bounds", utils::histogram(passwords.iter().map(|pr| pr.rules.bounds.1))),
+        ("letters", utils::histogram(passwords.iter().map(|pr| pr.rules.element))),
+        ("shortest password", lengths.iter().min().copied().into()),
+        ("longest password", lengths.iter().max().copied().into()),
+    ]))
 }
 
 
-pub fn day2_1() {
-    println!("First let's just do the test and see if we can parse the password rules:");
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let passwords = utils::parse_lines::<PasswordRules>(input)?;
+    Ok(Solution::new(passwords.iter().filter(|pr| validate_password(pr)).count()))
+}
+
+
+// one password at a time, as it is read
+pub fn solve_streaming(source: &str, input: &mut dyn BufRead, _ctx: &Context)
+    -> Result<Solution, SolveError>
+{
+    let mut valid = 0;
+    for pr in utils::stream_lines::<PasswordRules, _>(source, input) {
+        if validate_password(&pr?) {
+            valid += 1;
+        }
+    }
+    Ok(Solution::new(valid))
+}
+
+
+pub fn day2_1(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "First let's just do the test and see if we can parse the password rules:");
     let v = "1-3 c".parse::<Rules>().unwrap();
-    println!("The rules are: {0}", v);
-    println!("Now try to parse a full set of Password and rules.");
+    say!(ctx, "The rules are: {0}", v);
+    say!(ctx, "Now try to parse a full set of Password and rules.");
     let p = "1-3 c: abcdceec".parse::<PasswordRules>().unwrap();
-    println!("The password + rules are {0}", p);
+    say!(ctx, "The password + rules are {0}", p);
     // now let's see if that password is validate
     if validate_password(&p) {
-        println!("{0} is valid", p);
+        say!(ctx, "{0} is valid", p);
     } else {
-        println!("{0} is not valid", p);
+        say!(ctx, "{0} is not valid", p);
     }
     // now do the block from above.
-    let num_valid = PASSWORDS
-        .split("\n")
-        .map(|l| l.parse::<PasswordRules>())
-        .filter(|prr| prr.is_ok())
-        .map(|prr| prr.unwrap())
-        .filter(|pr| validate_password(pr))
+    let num_valid = puzzles::example(2, "passwords")
+        .unwrap()
+        .lines()
+        .filter_map(|l| l.parse::<PasswordRules>().ok())
+        .filter(validate_password)
         .count();
-    println!("valid passwords from PASSWORDS: {0:?}", num_valid);
+    ctx.answer("valid passwords in the example", num_valid);
 
     // finally let's process the input file
-    println!("\nDoing the input file...");
+    say!(ctx, "\nDoing the input file...");
 
-    let parsed_passwords = utils::read_file::<PasswordRules>("./input/day2-1.txt");
+    // skip (but mention) any lines that don't parse
+    let (parsed_passwords, failures) =
+        utils::read_file_lenient::<PasswordRules>("./input/day2-1.txt")?;
+    for failure in &failures {
+        say!(ctx, "skipping {}", failure);
+    }
     let num_valid_file = parsed_passwords
         .iter()
-        .filter(|&prr| prr.is_ok())
-        .cloned()
-        .map(|prr| prr.unwrap())
         .filter(|pr| validate_password(pr))
         .count();
         //.collect::<Vec<_>>();
-    println!("valid passwords in the file are: {0:?}", num_valid_file);
-
+    ctx.answer_for_input("valid passwords in the file", num_valid_file);
+    Ok(())
 }
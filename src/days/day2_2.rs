@@ -1,34 +1,26 @@
 // Day 2 Part 2 - Password Philosophy with a change
 //
+// The puzzle and its examples are in puzzles/day2.md.
 
-//1-3 a: abcde
-//1-3 b: cdefg
-//2-9 c: ccccccccc
-
-
-
+use std::io::BufRead;
 use std::str::FromStr;
 use std::fmt;
 
 use thiserror::Error;
 
-use crate::utils;
-
-
-const PASSWORDS: &str = "1-3 a: abcde\n\
-    1-3 b: cdefg\n\
-    2-9 c: ccccccccc\n\
-    ";
+use crate::context::Context;
+use crate::puzzles;
+use crate::registry::{SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Error, Debug, Clone)]
 pub enum PasswordError {
-    #[error("corrupted password line")]
+    #[error("corrupted password line: {0}")]
     DecodeError(String),
-    #[error("corrupted password rules")]
+    #[error("corrupted password rules: {0}")]
     RulesError(String),
-    #[error("unknown password error")]
-    Unknown,
 }
 
 
@@ -44,7 +36,7 @@ impl fmt::Display for Rules {
         // Write strictly the first element into the supplied output
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
-        // is very similar to `println!`.
+        // is very similar to `format!`.
         write!(f, "char: {0}, range: {1}-{2}", self.element, self.bounds.0, self.bounds.1)
     }
 }
@@ -55,11 +47,11 @@ impl FromStr for Rules {
 
     // parses a '1-3 c' into a Rules of bounds (1,3) and element 'c'
     fn from_str(rules: &str) -> Result<Self, Self::Err> {
-        let parts = rules.split(" ").collect::<Vec<_>>();
+        let parts = rules.split(' ').collect::<Vec<_>>();
         if parts.len() != 2 {
             return Err(PasswordError::DecodeError(format!("Missing space in passed string: {}", rules)));
         }
-        let bounds = parts[0].split("-").collect::<Vec<_>>();
+        let bounds = parts[0].split('-').collect::<Vec<_>>();
         if bounds.len() != 2 {
             return Err(PasswordError::RulesError(format!("Missing - in passed bounds: {}", parts[0])));
         }
@@ -69,12 +61,12 @@ impl FromStr for Rules {
         let lv = lb?;
         let uv = ub?;
         if lv >= uv {
-            return Err(PasswordError::RulesError(format!("Rules: lower bound can't be higher than upper bound!")));
+            return Err(PasswordError::RulesError("Rules: lower bound can't be higher than upper bound!".to_string()));
         }
         // now extract the character.
         let chars = parts[1].chars().collect::<Vec<_>>();
         if chars.len() != 1 {
-            return Err(PasswordError::DecodeError(format!("element rule must be a single character")));
+            return Err(PasswordError::DecodeError("element rule must be a single character".to_string()));
         }
         Ok(Rules{element: chars[0], bounds: (lv, uv)})
     }
@@ -94,7 +86,7 @@ impl fmt::Display for PasswordRules {
         // Write strictly the first element into the supplied output
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
-        // is very similar to `println!`.
+        // is very similar to `format!`.
         write!(f, "password: {0}, rules: {1}", self.password, self.rules)
     }
 }
@@ -124,49 +116,70 @@ impl FromStr for PasswordRules {
 fn validate_password(pr: &PasswordRules) -> bool {
     let f1 = pr.password.chars().nth((pr.rules.bounds.0 as usize)-1)
         .map(|c| c == pr.rules.element)
-        .unwrap_or_else(|| false);
+        .unwrap_or(false);
     let f2 = pr.password.chars().nth((pr.rules.bounds.1 as usize)-1)
         .map(|c| c == pr.rules.element)
-        .unwrap_or_else(|| false);
+        .unwrap_or(false);
     f1 ^ f2
 }
 
 
-pub fn day2_2() {
-    println!("First let's just do the test and see if we can parse the password rules:");
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let passwords = utils::parse_lines::<PasswordRules>(input)?;
+    Ok(Solution::new(passwords.iter().filter(|pr| validate_password(pr)).count()))
+}
+
+
+// one password at a time, as it is read
+pub fn solve_streaming(source: &str, input: &mut dyn BufRead, _ctx: &Context)
+    -> Result<Solution, SolveError>
+{
+    let mut valid = 0;
+    for pr in utils::stream_lines::<PasswordRules, _>(source, input) {
+        if validate_password(&pr?) {
+            valid += 1;
+        }
+    }
+    Ok(Solution::new(valid))
+}
+
+
+pub fn day2_2(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "First let's just do the test and see if we can parse the password rules:");
     let v = "1-3 c".parse::<Rules>().unwrap();
-    println!("The rules are: {0}", v);
-    println!("Now try to parse a full set of Password and rules.");
+    say!(ctx, "The rules are: {0}", v);
+    say!(ctx, "Now try to parse a full set of Password and rules.");
     let p = "1-3 c: abcdceec".parse::<PasswordRules>().unwrap();
-    println!("The password + rules are {0}", p);
+    say!(ctx, "The password + rules are {0}", p);
     // now let's see if that password is validate
     if validate_password(&p) {
-        println!("{0} is valid", p);
+        say!(ctx, "{0} is valid", p);
     } else {
-        println!("{0} is not valid", p);
+        say!(ctx, "{0} is not valid", p);
     }
     // now do the block from above.
-    let num_valid = PASSWORDS
-        .split("\n")
-        .map(|l| l.parse::<PasswordRules>())
-        .filter(|prr| prr.is_ok())
-        .map(|prr| prr.unwrap())
-        .filter(|pr| validate_password(pr))
+    let num_valid = puzzles::example(2, "passwords")
+        .unwrap()
+        .lines()
+        .filter_map(|l| l.parse::<PasswordRules>().ok())
+        .filter(validate_password)
         .count();
-    println!("valid passwords from PASSWORDS: {0:?}", num_valid);
+    ctx.answer("valid passwords in the example", num_valid);
 
     // finally let's process the input file
-    println!("\nDoing the input file...");
+    say!(ctx, "\nDoing the input file...");
 
-    let parsed_passwords = utils::read_file::<PasswordRules>("./input/day2-1.txt");
+    // skip (but mention) any lines that don't parse
+    let (parsed_passwords, failures) =
+        utils::read_file_lenient::<PasswordRules>("./input/day2-1.txt")?;
+    for failure in &failures {
+        say!(ctx, "skipping {}", failure);
+    }
     let num_valid_file = parsed_passwords
         .iter()
-        .filter(|&prr| prr.is_ok())
-        .cloned()
-        .map(|prr| prr.unwrap())
         .filter(|pr| validate_password(pr))
         .count();
         //.collect::<Vec<_>>();
-    println!("valid passwords in the file are: {0:?}", num_valid_file);
-
+    ctx.answer_for_input("valid passwords in the file", num_valid_file);
+    Ok(())
 }
@@ -1,24 +1,13 @@
-use std::str::FromStr;
 use std::fmt;
 
 use thiserror::Error;
 
-use crate::utils;
-
-
-const MAP: &str =
-   "..##.......\n\
-    #...#...#..\n\
-    .#....#..#.\n\
-    ..#.#...#.#\n\
-    .#...##..#.\n\
-    ..#.##.....\n\
-    .#.#.#....#\n\
-    .#........#\n\
-    #.##...#...\n\
-    #...##....#\n\
-    .#..#...#.#\n\
-    ";
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::puzzles;
+use crate::registry::{BadLine, Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Debug)]
@@ -66,7 +55,7 @@ impl fmt::Display for Map {
         // Write strictly the first element into the supplied output
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
-        // is very similar to `println!`.
+        // is very similar to `format!`.
         let header = format!("{}\n", "-".repeat(self.width + 2));
         let lines = self.trees
             .iter()
@@ -92,21 +81,35 @@ impl Map {
 }
 
 
+impl From<&Map> for Value {
+    // the trees row by row, true where there is a tree
+    fn from(map: &Map) -> Self {
+        json::object(vec![
+            ("width", map.width.into()),
+            ("height", map.height.into()),
+            ("trees", map.trees.clone().into()),
+        ])
+    }
+}
+
+
 #[derive(Error, Debug, Clone)]
 pub enum MapError {
-    #[error("corrupted map line")]
+    #[error("corrupted map line: {0}")]
     DecodeError(String),
-    #[error("not a square")]
+    #[error("not a square: {0}")]
     NotSquareError(String),
-    #[error("unknown map error")]
-    Unknown,
 }
 
 
 fn parse_line(line: &str) -> Result<Vec<bool>, MapError> {
-    Ok(line.chars()
-        .map(|c| c == '#')
-        .collect::<Vec<_>>())
+    line.chars()
+        .map(|c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _   => Err(MapError::DecodeError(format!("invalid char '{}' in line: {}", c, line))),
+        })
+        .collect::<Result<Vec<_>, _>>()
 }
 
 
@@ -117,28 +120,24 @@ fn parse_lines<I>(lines: I) -> Result<Map, MapError>
 {
     let trees = lines
         .into_iter()
-        .map(|s| parse_line(s.as_ref()).unwrap())  // we ought to do some error handling here!
-        .collect::<Vec<_>>();
+        .map(|s| parse_line(s.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
 
     let height = trees.len();
-    let width = trees.first().unwrap().len();
+    let width = trees.first().map(|l| l.len()).unwrap_or(0);
+    if trees.iter().any(|l| l.len() != width) {
+        return Err(MapError::NotSquareError("map lines are not all the same width".to_string()));
+    }
 
     Ok(Map {trees, height, width})
 }
 
 
-fn readfile_to_map(file_name: &str) -> Result<Map, MapError>
+fn readfile_to_map(file_name: &str) -> Result<Map, InputError>
 {
-    let trees = std::fs::read_to_string(file_name)
-        .expect("file not found!")
-        .lines()
-        .map(|s| parse_line(s).unwrap())
-        .collect::<Vec<_>>();
-
-    let height = trees.len();
-    let width = trees.first().unwrap().len();
-
-    Ok(Map {trees, height, width})
+    let data = utils::read_input(file_name)?;
+    parse_lines(data.lines())
+        .map_err(|e| InputError::Invalid { path: file_name.to_string(), error: e.to_string() })
 }
 
 
@@ -149,7 +148,7 @@ fn count_trees(map: &Map, delta: &Delta) -> u32 {
         if map.is_tree(at.x, at.y) {
             count += 1;
         }
-        at.go(&delta);
+        at.go(delta);
         if at.y >= map.height {
             break;
         }
@@ -158,19 +157,71 @@ fn count_trees(map: &Map, delta: &Delta) -> u32 {
 }
 
 
-pub fn day3_1() {
-    println!("Day 3_1.");
-    let map = parse_lines(MAP.lines()).unwrap();
-    println!("{}", map);
-    println!("Attempt the 3 right, 1 down thing...");
+// only the parser: every line should be a row of the map, all the same width as the first (so a
+// blank line is a row with no width).
+pub fn check(input: &str) -> Checked {
+    let mut width = None;
+    let mut checked = 0;
+    let mut bad = Vec::new();
+    for (n, line) in input.lines().enumerate() {
+        checked += 1;
+        let error = match parse_line(line) {
+            Err(e) => e,
+            Ok(row) => match width {
+                None => {
+                    width = Some(row.len());
+                    continue;
+                },
+                Some(w) if w == row.len() => continue,
+                Some(w) => MapError::NotSquareError(format!("row is {} wide, expected {}", row.len(), w)),
+            },
+        };
+        bad.push(BadLine { line: n + 1, text: line.to_string(), error: error.to_string() });
+    }
+    Checked { checked, bad }
+}
+
+
+// the map of trees
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    let map = parse_lines(input.lines()).map_err(|e| SolveError::ParseError(e.to_string()))?;
+    Ok((&map).into())
+}
 
-    let delta = Delta::new(3, 1);
-    println!("found {} trees", count_trees(&map, &delta));
 
-    // okay, now try the version on disk
-    let rmap = readfile_to_map("./input/day3-1.txt").unwrap();
-    println!("\n{}\n", rmap);
-    println!("found {} trees for the on disk trees.", count_trees(&rmap, &delta));
+// the size of the map and how thick the trees are
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let map = parse_lines(input.lines()).map_err(|e| SolveError::ParseError(e.to_string()))?;
+    let trees = map.trees.iter().flatten().filter(|t| **t).count();
+    let squares = map.width * map.height;
+    let density = if squares == 0 { 0.0 } else { trees as f64 / squares as f64 };
+    Ok(json::object(vec![
+        ("width", map.width.into()),
+        ("height", map.height.into()),
+        ("trees", trees.into()),
+        ("tree density", format!("{:.3}", density).into()),
+    ]))
+}
 
 
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let map = parse_lines(input.lines()).map_err(|e| SolveError::ParseError(e.to_string()))?;
+    Ok(Solution::new(count_trees(&map, &Delta::new(3, 1))))
+}
+
+
+pub fn day3_1(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 3_1.");
+    let map = parse_lines(puzzles::example(3, "map").unwrap().lines()).unwrap();
+    say!(ctx, "{}", map);
+    say!(ctx, "Attempt the 3 right, 1 down thing...");
+
+    let delta = Delta::new(3, 1);
+    ctx.answer("trees found", count_trees(&map, &delta));
+
+    // okay, now try the version on disk
+    let rmap = readfile_to_map("./input/day3-1.txt")?;
+    say!(ctx, "\n{}\n", rmap);
+    ctx.answer_for_input("trees found on disk", count_trees(&rmap, &delta));
+    Ok(())
 }
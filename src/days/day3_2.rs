@@ -1,24 +1,12 @@
-use std::str::FromStr;
 use std::fmt;
 
 use thiserror::Error;
 
-use crate::utils;
-
-
-const MAP: &str =
-   "..##.......\n\
-    #...#...#..\n\
-    .#....#..#.\n\
-    ..#.#...#.#\n\
-    .#...##..#.\n\
-    ..#.##.....\n\
-    .#.#.#....#\n\
-    .#........#\n\
-    #.##...#...\n\
-    #...##....#\n\
-    .#..#...#.#\n\
-    ";
+use crate::context::Context;
+use crate::puzzles;
+use crate::registry::{SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Debug)]
@@ -66,7 +54,7 @@ impl fmt::Display for Map {
         // Write strictly the first element into the supplied output
         // stream: `f`. Returns `fmt::Result` which indicates whether the
         // operation succeeded or failed. Note that `write!` uses syntax which
-        // is very similar to `println!`.
+        // is very similar to `format!`.
         let header = format!("{}\n", "-".repeat(self.width + 2));
         let lines = self.trees
             .iter()
@@ -94,19 +82,21 @@ impl Map {
 
 #[derive(Error, Debug, Clone)]
 pub enum MapError {
-    #[error("corrupted map line")]
+    #[error("corrupted map line: {0}")]
     DecodeError(String),
-    #[error("not a square")]
+    #[error("not a square: {0}")]
     NotSquareError(String),
-    #[error("unknown map error")]
-    Unknown,
 }
 
 
 fn parse_line(line: &str) -> Result<Vec<bool>, MapError> {
-    Ok(line.chars()
-        .map(|c| c == '#')
-        .collect::<Vec<_>>())
+    line.chars()
+        .map(|c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _   => Err(MapError::DecodeError(format!("invalid char '{}' in line: {}", c, line))),
+        })
+        .collect::<Result<Vec<_>, _>>()
 }
 
 
@@ -117,28 +107,24 @@ fn parse_lines<I>(lines: I) -> Result<Map, MapError>
 {
     let trees = lines
         .into_iter()
-        .map(|s| parse_line(s.as_ref()).unwrap())  // we ought to do some error handling here!
-        .collect::<Vec<_>>();
+        .map(|s| parse_line(s.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
 
     let height = trees.len();
-    let width = trees.first().unwrap().len();
+    let width = trees.first().map(|l| l.len()).unwrap_or(0);
+    if trees.iter().any(|l| l.len() != width) {
+        return Err(MapError::NotSquareError("map lines are not all the same width".to_string()));
+    }
 
     Ok(Map {trees, height, width})
 }
 
 
-fn readfile_to_map(file_name: &str) -> Result<Map, MapError>
+fn readfile_to_map(file_name: &str) -> Result<Map, InputError>
 {
-    let trees = std::fs::read_to_string(file_name)
-        .expect("file not found!")
-        .lines()
-        .map(|s| parse_line(s).unwrap())
-        .collect::<Vec<_>>();
-
-    let height = trees.len();
-    let width = trees.first().unwrap().len();
-
-    Ok(Map {trees, height, width})
+    let data = utils::read_input(file_name)?;
+    parse_lines(data.lines())
+        .map_err(|e| InputError::Invalid { path: file_name.to_string(), error: e.to_string() })
 }
 
 
@@ -149,7 +135,7 @@ fn count_trees(map: &Map, delta: &Delta) -> u32 {
         if map.is_tree(at.x, at.y) {
             count += 1;
         }
-        at.go(&delta);
+        at.go(delta);
         if at.y >= map.height {
             break;
         }
@@ -158,10 +144,26 @@ fn count_trees(map: &Map, delta: &Delta) -> u32 {
 }
 
 
-pub fn day3_2() {
-    println!("Day 3_2.");
-    let map = parse_lines(MAP.lines()).unwrap();
-    println!("Attempt to calculate the multiplication using the 5 deltas...");
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let map = parse_lines(input.lines()).map_err(|e| SolveError::ParseError(e.to_string()))?;
+    let deltas = [
+        Delta::new(1, 1),
+        Delta::new(3, 1),
+        Delta::new(5, 1),
+        Delta::new(7, 1),
+        Delta::new(1, 2)];
+    let product = deltas
+        .iter()
+        .map(|d| count_trees(&map, d) as u64)
+        .product::<u64>();
+    Ok(Solution::new(product))
+}
+
+
+pub fn day3_2(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 3_2.");
+    let map = parse_lines(puzzles::example(3, "map").unwrap().lines()).unwrap();
+    say!(ctx, "Attempt to calculate the multiplication using the 5 deltas...");
     let deltas = [
         Delta::new(1, 1),
         Delta::new(3, 1),
@@ -172,16 +174,17 @@ pub fn day3_2() {
     let result = deltas
         .iter()
         .map(|d| count_trees(&map, d))
-        .fold(1, |prod, v| prod * v);
+        .product::<u32>();
 
-    println!("for all deltas, result = {}", result);
+    ctx.answer("for all deltas, result", result);
 
-    let rmap = readfile_to_map("./input/day3-1.txt").unwrap();
+    let rmap = readfile_to_map("./input/day3-1.txt")?;
     let problem = deltas
         .iter()
         .map(|d| count_trees(&rmap, d) as u64)
-        .fold(1, |prod, v| prod * v);
+        .product::<u64>();
 
-    println!("for supplied map, result = {}", problem);
+    ctx.answer_for_input("for supplied map, result", problem);
+    Ok(())
 }
 
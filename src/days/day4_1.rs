@@ -1,40 +1,26 @@
 use std::str::FromStr;
-use std::fmt;
 use std::collections::HashMap;
 
 
 use thiserror::Error;
 
-use crate::utils;
-
-
-const PASSPORTS: &str =
-   "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
-    byr:1937 iyr:2017 cid:147 hgt:183cm\n\
-    \n\
-    iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\n\
-    hcl:#cfa07d byr:1929\n\
-    \n\
-    hcl:#ae17e1 iyr:2013\n\
-    eyr:2024\n\
-    ecl:brn pid:760753108 byr:1931\n\
-    hgt:179cm\n\
-    \n\
-    hcl:#cfa07d eyr:2025 pid:166559648\n\
-    iyr:2011 ecl:brn hgt:59in\n\
-    ";
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::puzzles;
+use crate::registry::{BadLine, Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, FromBatch, InputError};
 
 
 #[derive(Error, Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum PassportError {
-    #[error("Missing fields")]
+    #[error("Missing fields: {0}")]
     MissingError(String),
-    #[error("corrupted passport line")]
+    #[error("corrupted passport line: {0}")]
     CorruptedError(String),
-    #[error("Couldn't decode part")]
+    #[error("Couldn't decode part: {0}")]
     ParseError(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
@@ -59,14 +45,14 @@ impl FromStr for Passport {
     // Note that cid is optional
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         // split each part from the others so that they can be collected.
-        let parts = line.split(" ").collect::<Vec<_>>();
+        let parts = line.split(' ').collect::<Vec<_>>();
 
         let mandatory = ["ecl", "pid", "eyr", "hcl", "byr", "iyr", "hgt"];
 
         // extract all the named bits.
         let mut matches = HashMap::new();
         for part in parts {
-            let bits = part.split(":").collect::<Vec<_>>();
+            let bits = part.split(':').collect::<Vec<_>>();
             if bits.len() != 2 {
                 return Err(PassportError::CorruptedError(format!("part '{}' is corrupt?", part)));
             }
@@ -83,7 +69,7 @@ impl FromStr for Passport {
                 missing_keys.push(key.to_string());
             }
         }
-        if missing_keys.len() != 0 {
+        if !missing_keys.is_empty() {
             return Err(PassportError::MissingError(format!("missing keys in passport: {}",
                         missing_keys.join(", "))));
         }
@@ -113,40 +99,156 @@ impl FromStr for Passport {
     }
 }
 
-// restructure in the input into a series of lines of passport
-fn restructure_input(input: &str) -> Vec<String> {
-    let mut line = String::new();
-    let mut lines = Vec::new();
-    for l in input.lines() {
-        let stripped = l.trim();
-        if l.is_empty() {
-            lines.push(line.clone());
-            line.clear();
-        } else {
-            if !line.is_empty() {
-                line.push(' ');
-            }
-            line.push_str(stripped);
-        }
+// heights are a number and a unit, e.g. '183cm' or '59in'; either may be missing.
+fn split_height(hgt: &str) -> (Option<u32>, Option<&str>) {
+    let digits = hgt.chars().take_while(|c| c.is_ascii_digit()).count();
+    let (value, unit) = hgt.split_at(digits);
+    (value.parse::<u32>().ok(), if unit.is_empty() { None } else { Some(unit) })
+}
+
+
+impl From<&Passport> for Value {
+    fn from(p: &Passport) -> Self {
+        let (height, unit) = split_height(&p.hgt);
+        json::object(vec![
+            ("ecl", p.ecl.as_str().into()),
+            ("pid", p.pid.as_str().into()),
+            ("eyr", p.eyr.into()),
+            ("hcl", p.hcl.as_str().into()),
+            ("byr", p.byr.into()),
+            ("iyr", p.iyr.into()),
+            ("cid", p.cid.into()),
+            ("hgt", json::object(vec![
+                ("text", p.hgt.as_str().into()),
+                ("value", height.into()),
+                ("unit", unit.into()),
+            ])),
+        ])
     }
-    if !line.is_empty() {
-        lines.push(line.clone());
+}
+
+impl FromBatch for Passport {
+    type Err = PassportError;
+
+    fn from_batch(lines: &[&str]) -> Result<Self, Self::Err> {
+        utils::join_batch(lines).parse()
     }
-    lines
 }
 
 
-pub fn day4_1() {
-    println!("Day 4_1.");
-    let passport_lines = restructure_input(PASSPORTS);
-    println!("{}", passport_lines.join("\n"));
-    // parse the passport_lines into passports.
-    let passports = passport_lines
+// only the parser: a passport with missing fields is just not valid, but one whose parts aren't
+// 'key:value' is broken.
+pub fn check(input: &str) -> Checked {
+    let lines = input.lines().collect::<Vec<_>>();
+    let passports = utils::read_batches::<Passport>(input).collect::<Vec<_>>();
+    let bad = passports
         .iter()
-        .map(|l| l.parse::<Passport>())
+        .filter_map(|(range, passport)| match passport {
+            Err(e @ PassportError::CorruptedError(_)) => Some(BadLine {
+                line: *range.start(),
+                text: utils::join_batch(&lines[range.start() - 1..*range.end()]),
+                error: format!("passport on {}: {}", utils::line_range(range), e),
+            }),
+            _ => None,
+        })
+        .collect();
+    Checked { checked: passports.len(), bad }
+}
+
+
+// the passports that have all their fields, numbered in the order they appear, and the reason each
+// of the others was rejected.
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    let mut passports = Vec::new();
+    let mut rejected = Vec::new();
+    for (n, (lines, passport)) in utils::read_batches::<Passport>(input).enumerate() {
+        match passport {
+            Ok(passport) => {
+                let mut record = vec![("passport".to_string(), (n + 1).into())];
+                if let Value::Object(fields) = Value::from(&passport) {
+                    record.extend(fields);
+                }
+                passports.push(Value::Object(record));
+            },
+            Err(PassportError::CorruptedError(e)) => return Err(SolveError::ParseError(
+                format!("passport {} ({}): {}", n + 1, utils::line_range(&lines), e))),
+            Err(e) => rejected.push(json::object(vec![
+                ("passport", (n + 1).into()),
+                ("reason", e.to_string().into()),
+            ])),
+        }
+    }
+    Ok(json::object(vec![("passports", passports.into()), ("rejected", rejected.into())]))
+}
+
+
+// the names of the fields in a passport, whether or not it has the ones it needs
+struct Fields(Vec<String>);
+
+
+impl FromBatch for Fields {
+    type Err = PassportError;
+
+    fn from_batch(lines: &[&str]) -> Result<Self, Self::Err> {
+        lines
+            .iter()
+            .flat_map(|l| l.split_whitespace())
+            .map(|part| match part.split_once(':') {
+                Some((name, _)) => Ok(name.to_lowercase()),
+                None => Err(PassportError::CorruptedError(format!("part '{}' is corrupt?", part))),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Fields)
+    }
+}
+
+
+// how often each field turns up, and how many passports have all the ones they need
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let mut fields = Vec::new();
+    for (lines, names) in utils::read_batches::<Fields>(input) {
+        let Fields(names) = names.map_err(
+            |e| SolveError::ParseError(format!("{}: {}", utils::line_range(&lines), e)))?;
+        fields.extend(names);
+    }
+    let passports = utils::read_batches::<Passport>(input).collect::<Vec<_>>();
+    let complete = passports.iter().filter(|(_, p)| p.is_ok()).count();
+    Ok(json::object(vec![
+        ("passports", passports.len().into()),
+        ("with every needed field", complete.into()),
+        ("fields", utils::histogram(fields)),
+    ]))
+}
+
+
+// passports with missing or invalid fields are just not valid, but a batch that isn't made of
+// 'key:value' parts means the input is broken.  The rejected passports are the evidence.
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let mut count = 0;
+    let mut rejected = Vec::new();
+    for (n, (lines, passport)) in utils::read_batches::<Passport>(input).enumerate() {
+        match passport {
+            Ok(_) => count += 1,
+            Err(PassportError::CorruptedError(e)) => return Err(SolveError::ParseError(
+                format!("passport {} ({}): {}", n + 1, utils::line_range(&lines), e))),
+            Err(e) => rejected.push(json::object(vec![
+                ("passport", (n + 1).into()),
+                ("reason", e.to_string().into()),
+            ])),
+        }
+    }
+    Ok(Solution::new(count).with("rejected", rejected))
+}
+
+
+pub fn day4_1(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 4_1.");
+    // parse the example's batches of lines into passports.
+    let passports = utils::read_batches::<Passport>(puzzles::example(4, "passports").unwrap())
+        .map(|(_, passport)| passport)
         .collect::<Vec<_>>();
     for passport in &passports {
-        println!("{:?}", passport);
+        say!(ctx, "{:?}", passport);
     }
     let mut count: usize = 0;
     for passport in &passports {
@@ -154,14 +256,12 @@ pub fn day4_1() {
             count += 1;
         }
     }
-    println!("Valid passports in test: {}", count);
+    ctx.answer("Valid passports in test", count);
 
     // now lets count the ones in the file.
-    let rpassport_data = std::fs::read_to_string("./input/day4-1.txt").unwrap();
-    let rpassport_lines = restructure_input(&rpassport_data);
-    let rpassports = rpassport_lines
-        .iter()
-        .map(|l| l.parse::<Passport>())
+    let rpassport_data = utils::read_input("./input/day4-1.txt")?;
+    let rpassports = utils::read_batches::<Passport>(&rpassport_data)
+        .map(|(_, passport)| passport)
         .collect::<Vec<_>>();
 
     let mut count: usize = 0;
@@ -170,8 +270,8 @@ pub fn day4_1() {
             count += 1;
         }
     }
-    println!("Valid passports in file: {}", count);
-
+    ctx.answer_for_input("Valid passports in file", count);
+    Ok(())
 }
 
 
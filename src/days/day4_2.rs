@@ -1,42 +1,27 @@
 use std::str::FromStr;
-use std::fmt;
 use std::collections::HashMap;
 
 
 use thiserror::Error;
 
-use crate::utils;
-
-
-const PASSPORTS: &str =
-   "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\n\
-    byr:1937 iyr:2017 cid:147 hgt:183cm\n\
-    \n\
-    iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884\n\
-    hcl:#cfa07d byr:1929\n\
-    \n\
-    hcl:#ae17e1 iyr:2013\n\
-    eyr:2024\n\
-    ecl:brn pid:760753108 byr:1931\n\
-    hgt:179cm\n\
-    \n\
-    hcl:#cfa07d eyr:2025 pid:166559648\n\
-    iyr:2011 ecl:brn hgt:59in\n\
-    ";
+use crate::context::Context;
+use crate::json;
+use crate::puzzles;
+use crate::registry::{SolveError, Solution};
+use crate::say;
+use crate::utils::{self, FromBatch, InputError};
 
 
 #[derive(Error, Debug, Clone)]
 pub enum PassportError {
-    #[error("Missing fields")]
+    #[error("Missing fields: {0}")]
     MissingError(String),
-    #[error("corrupted passport line")]
+    #[error("corrupted passport line: {0}")]
     CorruptedError(String),
-    #[error("Couldn't decode part")]
+    #[error("Couldn't decode part: {0}")]
     ParseError(String),
-    #[error("Not valid for part")]
+    #[error("Not valid for part: {0}")]
     InvalidPart(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
@@ -48,6 +33,7 @@ enum HeightUnit {
 
 
 #[derive(Debug)]
+#[allow(dead_code)]
 struct Passport {
     ecl: String,
     pid: String,
@@ -69,14 +55,14 @@ impl FromStr for Passport {
     // Note that cid is optional
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         // split each part from the others so that they can be collected.
-        let parts = line.split(" ").collect::<Vec<_>>();
+        let parts = line.split(' ').collect::<Vec<_>>();
 
         let mandatory = ["ecl", "pid", "eyr", "hcl", "byr", "iyr", "hgt"];
 
         // extract all the named bits.
         let mut matches = HashMap::new();
         for part in parts {
-            let bits = part.split(":").collect::<Vec<_>>();
+            let bits = part.split(':').collect::<Vec<_>>();
             if bits.len() != 2 {
                 return Err(PassportError::CorruptedError(format!("part '{}' is corrupt?", part)));
             }
@@ -93,13 +79,13 @@ impl FromStr for Passport {
                 missing_keys.push(key.to_string());
             }
         }
-        if missing_keys.len() != 0 {
+        if !missing_keys.is_empty() {
             return Err(PassportError::MissingError(format!("missing keys in passport: {}",
                         missing_keys.join(", "))));
         }
         // finally, let's build the passport (with the optional cid) -- this may error if it can't
         // parse the relevant bits.
-        // validate ecl is one of the hair colours allowed.
+        // validate ecl is one of the eye colours allowed.
         let ecl = matches.get("ecl").unwrap().to_string();
         let valid_ecl = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
         let mut valid = false;
@@ -110,7 +96,7 @@ impl FromStr for Passport {
             }
         }
         if !valid {
-            return Err(PassportError::InvalidPart(format!("ecl - invalid hair colour: {}", ecl)));
+            return Err(PassportError::InvalidPart(format!("ecl - invalid eye colour: {}", ecl)));
         }
 
         // validate the passport ID - a nine-digit number, including leading zeroes.
@@ -121,7 +107,7 @@ impl FromStr for Passport {
         }
         let mut invalid_pid_chars: Vec<char> = Vec::new();
         for pid_char in pid_chars {
-            if !pid_char.is_digit(10) {
+            if !pid_char.is_ascii_digit() {
                 invalid_pid_chars.push(pid_char);
             }
         }
@@ -133,7 +119,7 @@ impl FromStr for Passport {
         let eyr = matches.get("eyr").unwrap().parse::<u32>().map_err(
             |e| PassportError::ParseError(format!("eyr parse error on {}: {}",
                     matches.get("eyr").unwrap(), e)))?;
-        if eyr < 2020 || eyr > 2030 {
+        if !(2020..=2030).contains(&eyr) {
             return Err(PassportError::InvalidPart(format!("eyr invalid year: {}", eyr)));
         }
         // validate haircolour - string in form '#abcdef (hex)
@@ -158,7 +144,7 @@ impl FromStr for Passport {
         let byr = matches.get("byr").unwrap().parse::<u32>().map_err(
             |e| PassportError::ParseError(format!("byr parse error on {}: {}",
                     matches.get("byr").unwrap(), e)))?;
-        if byr < 1919 || byr > 2002 {
+        if !(1919..=2002).contains(&byr) {
             return Err(PassportError::InvalidPart(format!("byr invalid year: {}", byr)));
         }
 
@@ -166,7 +152,7 @@ impl FromStr for Passport {
         let iyr = matches.get("iyr").unwrap().parse::<u32>().map_err(
             |e| PassportError::ParseError(format!("iyr parse error on {}: {}",
                     matches.get("iyr").unwrap(), e)))?;
-        if iyr < 2010 || iyr > 2020 {
+        if !(2010..=2020).contains(&iyr) {
             return Err(PassportError::InvalidPart(format!("iyr invalid year: {}", iyr)));
         }
 
@@ -189,14 +175,14 @@ impl FromStr for Passport {
                 "hgt parse error: can't get number from {} (tried {}): {}", hgt, height_str, e)))?;
         if last_two == "ni" {
             // deal with inches
-            if height < 59 || height > 76 {
+            if !(59..=76).contains(&height) {
                 return Err(PassportError::InvalidPart(format!("hgt: height is not in range {} in",
                             height)));
             }
         } else if last_two == "mc" {
             // deal with cm
-            if height < 150 || height > 193 {
-                return Err(PassportError::InvalidPart(format!("hgt: height is not in range {} in",
+            if !(150..=193).contains(&height) {
+                return Err(PassportError::InvalidPart(format!("hgt: height is not in range {} cm",
                             height)));
             }
         } else {
@@ -208,40 +194,44 @@ impl FromStr for Passport {
     }
 }
 
-// restructure in the input into a series of lines of passport
-fn restructure_input(input: &str) -> Vec<String> {
-    let mut line = String::new();
-    let mut lines = Vec::new();
-    for l in input.lines() {
-        let stripped = l.trim();
-        if l.is_empty() {
-            lines.push(line.clone());
-            line.clear();
-        } else {
-            if !line.is_empty() {
-                line.push(' ');
-            }
-            line.push_str(stripped);
-        }
+impl FromBatch for Passport {
+    type Err = PassportError;
+
+    fn from_batch(lines: &[&str]) -> Result<Self, Self::Err> {
+        utils::join_batch(lines).parse()
     }
-    if !line.is_empty() {
-        lines.push(line.clone());
+}
+
+
+// passports with missing or invalid fields are just not valid, but a batch that isn't made of
+// 'key:value' parts means the input is broken.  The rejected passports are the evidence.
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let mut count = 0;
+    let mut rejected = Vec::new();
+    for (n, (lines, passport)) in utils::read_batches::<Passport>(input).enumerate() {
+        match passport {
+            Ok(_) => count += 1,
+            Err(PassportError::CorruptedError(e)) => return Err(SolveError::ParseError(
+                format!("passport {} ({}): {}", n + 1, utils::line_range(&lines), e))),
+            Err(e) => rejected.push(json::object(vec![
+                ("passport", (n + 1).into()),
+                ("reason", e.to_string().into()),
+            ])),
+        }
     }
-    lines
+    Ok(Solution::new(count).with("rejected", rejected))
 }
 
 
-pub fn day4_2() {
-    println!("Day 4_2.");
-    let passport_lines = restructure_input(PASSPORTS);
-    println!("{}", passport_lines.join("\n"));
-    // parse the passport_lines into passports.
-    let passports = passport_lines
-        .iter()
-        .map(|l| l.parse::<Passport>())
+pub fn day4_2(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 4_2.");
+    // parse the example's batches of lines into passports.
+    let example = puzzles::example(4, "checked-passports").unwrap();
+    let passports = utils::read_batches::<Passport>(example)
+        .map(|(_, passport)| passport)
         .collect::<Vec<_>>();
     for passport in &passports {
-        println!("{:?}", passport);
+        say!(ctx, "{:?}", passport);
     }
     let mut count: usize = 0;
     for passport in &passports {
@@ -249,14 +239,12 @@ pub fn day4_2() {
             count += 1;
         }
     }
-    println!("Valid passports in test: {}", count);
+    ctx.answer("Valid passports in test", count);
 
     // now lets count the ones in the file.
-    let rpassport_data = std::fs::read_to_string("./input/day4-1.txt").unwrap();
-    let rpassport_lines = restructure_input(&rpassport_data);
-    let rpassports = rpassport_lines
-        .iter()
-        .map(|l| l.parse::<Passport>())
+    let rpassport_data = utils::read_input("./input/day4-1.txt")?;
+    let rpassports = utils::read_batches::<Passport>(&rpassport_data)
+        .map(|(_, passport)| passport)
         .collect::<Vec<_>>();
 
     let mut count: usize = 0;
@@ -265,6 +253,6 @@ pub fn day4_2() {
             count += 1;
         }
     }
-    println!("Valid passports in file: {}", count);
-
+    ctx.answer_for_input("Valid passports in file", count);
+    Ok(())
 }
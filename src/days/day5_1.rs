@@ -1,11 +1,14 @@
+use std::io::BufRead;
 use std::str::FromStr;
-use std::fmt;
-use std::collections::HashMap;
 
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::registry::{Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Debug)]
@@ -18,10 +21,8 @@ struct Seat {
 
 #[derive(Error, Debug, Clone)]
 pub enum SeatError {
-    #[error("Corrupt boarding pass")]
+    #[error("corrupt boarding pass: {0}")]
     CorruptError(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
@@ -64,33 +65,101 @@ impl FromStr for Seat {
 }
 
 
+impl From<&Seat> for Value {
+    fn from(seat: &Seat) -> Self {
+        json::object(vec![
+            ("boarding_pass", seat.bp.as_str().into()),
+            ("row", seat.row.into()),
+            ("col", seat.col.into()),
+            ("id", seat_id(seat).into()),
+        ])
+    }
+}
+
+
 fn seat_id(seat: &Seat) -> u32 {
     seat.row * 8 + seat.col
 }
 
 
-pub fn day5_1() {
-    println!("Day 5_1.");
+// only the parser: every line should be a boarding pass
+pub fn check(input: &str) -> Checked {
+    utils::check_lines::<Seat>(input)
+}
+
+
+// the boarding passes decoded into seats
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    let seats = utils::parse_lines::<Seat>(input)?;
+    Ok(seats.iter().collect::<Vec<_>>().into())
+}
+
+
+// the range of the seats, and how many in that range have no boarding pass
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let seats = utils::parse_lines::<Seat>(input)?;
+    let ids = seats.iter().map(seat_id).collect::<Vec<_>>();
+    let (lowest, highest) = (ids.iter().min().copied(), ids.iter().max().copied());
+    let empty = match (lowest, highest) {
+        (Some(lowest), Some(highest)) => (lowest..=highest).filter(|id| !ids.contains(id)).count(),
+        _ => 0,
+    };
+    Ok(json::object(vec![
+        ("boarding passes", seats.len().into()),
+        ("lowest id", lowest.into()),
+        ("highest id", highest.into()),
+        ("empty seats between", empty.into()),
+    ]))
+}
+
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let seats = utils::parse_lines::<Seat>(input)?;
+    seats
+        .iter()
+        .map(seat_id)
+        .max()
+        .map(Solution::new)
+        .ok_or_else(|| SolveError::NoAnswer("there are no boarding passes".to_string()))
+}
+
+
+// one boarding pass at a time, as it is read
+pub fn solve_streaming(source: &str, input: &mut dyn BufRead, _ctx: &Context)
+    -> Result<Solution, SolveError>
+{
+    let mut highest = None;
+    for seat in utils::stream_lines::<Seat, _>(source, input) {
+        highest = highest.max(Some(seat_id(&seat?)));
+    }
+    highest
+        .map(Solution::new)
+        .ok_or_else(|| SolveError::NoAnswer("there are no boarding passes".to_string()))
+}
+
+
+pub fn day5_1(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 5_1.");
     let seat1 = consume_bp("FBFBBFFRLR").unwrap();
-    println!("{:?} code {}", seat1, seat_id(&seat1));
+    say!(ctx, "{:?} code {}", seat1, seat_id(&seat1));
     let seat2 = consume_bp("BFFFBBFRRR").unwrap();
-    println!("{:?} code {}", seat2, seat_id(&seat2));
+    say!(ctx, "{:?} code {}", seat2, seat_id(&seat2));
     let seat3 = consume_bp("FFFBBBFRRR").unwrap();
-    println!("{:?} code {}", seat3, seat_id(&seat3));
+    say!(ctx, "{:?} code {}", seat3, seat_id(&seat3));
     let seat4 = consume_bp("BBFFBBFRLL").unwrap();
-    println!("{:?} code {}", seat4, seat_id(&seat4));
+    say!(ctx, "{:?} code {}", seat4, seat_id(&seat4));
 
-    println!("let's grab the file and look at them");
-    let seats = utils::read_file::<Seat>("./input/day5-1.txt");
+    say!(ctx, "let's grab the file and look at them");
+    let seats = utils::read_file::<Seat>("./input/day5-1.txt")?;
     // find highest
     let mut max = 0;
-    let mut seatid = 0;
     for seat in seats {
-        seatid = seat_id(&(seat.unwrap()));
+        let seatid = seat_id(&seat);
         if seatid > max {
             max = seatid;
         }
     }
-    println!("highest seat id is {}", max);
+    ctx.answer_for_input("highest seat id", max);
+    Ok(())
 }
 
@@ -1,11 +1,15 @@
 use std::str::FromStr;
-use std::fmt;
 use std::collections::BTreeSet;
+use std::io::BufRead;
 
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::registry::{SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Debug)]
@@ -18,10 +22,8 @@ struct Seat {
 
 #[derive(Error, Debug, Clone)]
 pub enum SeatError {
-    #[error("Corrupt boarding pass")]
+    #[error("corrupt boarding pass: {0}")]
     CorruptError(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
@@ -69,41 +71,94 @@ fn seat_id(seat: &Seat) -> u32 {
 }
 
 
-pub fn day5_2() {
-    println!("Day 5_2.");
+fn seat_json(seat: &Seat) -> Value {
+    json::object(vec![
+        ("boarding_pass", seat.bp.as_str().into()),
+        ("row", seat.row.into()),
+        ("col", seat.col.into()),
+        ("id", seat_id(seat).into()),
+    ])
+}
+
+
+// the missing seat is the evidence, along with the seats either side of it.
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let seats = utils::parse_lines::<Seat>(input)?;
+    let seatids = seats
+        .iter()
+        .filter(|s| s.row != 0 && s.row != 127)
+        .map(seat_id)
+        .collect::<BTreeSet<_>>();
+    let mut last_seatid: u32 = 0;
+    for seatid in seatids {
+        if seatid == last_seatid + 2 {
+            let neighbour = |id| seats.iter().find(|s| seat_id(s) == id).map(seat_json);
+            return Ok(Solution::new(seatid - 1)
+                .with("seat", json::object(vec![
+                    ("row", ((seatid - 1) / 8).into()),
+                    ("col", ((seatid - 1) % 8).into()),
+                ]))
+                .with("before", neighbour(last_seatid))
+                .with("after", neighbour(seatid)));
+        }
+        last_seatid = seatid;
+    }
+    Err(SolveError::NoAnswer("no gap in the seat ids".to_string()))
+}
+
+
+// There are only 128 * 8 seats, so however many boarding passes there are, which seats are taken
+// fits in a fixed table; the missing seat is the first empty one with taken seats either side.
+pub fn solve_streaming(source: &str, input: &mut dyn BufRead, _ctx: &Context)
+    -> Result<Solution, SolveError>
+{
+    let mut taken = [false; 128 * 8];
+    for seat in utils::stream_lines::<Seat, _>(source, input) {
+        let seat = seat?;
+        if seat.row != 0 && seat.row != 127 {
+            taken[seat_id(&seat) as usize] = true;
+        }
+    }
+    (1..taken.len() - 1)
+        .find(|id| !taken[*id] && taken[id - 1] && taken[id + 1])
+        .map(Solution::new)
+        .ok_or_else(|| SolveError::NoAnswer("no gap in the seat ids".to_string()))
+}
+
+
+pub fn day5_2(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 5_2.");
     let seat1 = consume_bp("FBFBBFFRLR").unwrap();
-    println!("{:?} code {}", seat1, seat_id(&seat1));
+    say!(ctx, "{:?} code {}", seat1, seat_id(&seat1));
     let seat2 = consume_bp("BFFFBBFRRR").unwrap();
-    println!("{:?} code {}", seat2, seat_id(&seat2));
+    say!(ctx, "{:?} code {}", seat2, seat_id(&seat2));
     let seat3 = consume_bp("FFFBBBFRRR").unwrap();
-    println!("{:?} code {}", seat3, seat_id(&seat3));
+    say!(ctx, "{:?} code {}", seat3, seat_id(&seat3));
     let seat4 = consume_bp("BBFFBBFRLL").unwrap();
-    println!("{:?} code {}", seat4, seat_id(&seat4));
+    say!(ctx, "{:?} code {}", seat4, seat_id(&seat4));
 
-    println!("let's grab the file and look at them");
-    let seats = utils::read_file::<Seat>("./input/day5-1.txt");
+    say!(ctx, "let's grab the file and look at them");
+    let seats = utils::read_file::<Seat>("./input/day5-1.txt")?;
 
     // need to find seat_id which is missing, not on row 0 or row 128 and which has a seat_id -1
     // and +1.
     // use a set to collect the seats we want and then go through them (in order) 
     let mut seatids = BTreeSet::new();
-    let mut seatu: Seat;
     for seat in seats {
-        seatu = seat.unwrap();
-        if seatu.row == 0 || seatu.row == 127 {
+        if seat.row == 0 || seat.row == 127 {
             continue;
         }
-        seatids.insert(seat_id(&seatu));
+        seatids.insert(seat_id(&seat));
     }
 
     let mut last_seatid: u32 = 0;
     for seatid in seatids {
         if seatid == last_seatid + 2 {
-            println!("Candidate seatid is {}", seatid -1);
+            ctx.answer_for_input("Candidate seatid", seatid - 1);
         }
         last_seatid = seatid;
     }
-
+    Ok(())
 }
 
 
@@ -1,11 +1,14 @@
-use std::str::FromStr;
-use std::fmt;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::registry::{BadLine, Checked, SolveError, Solution};
+use crate::puzzles;
+use crate::say;
+use crate::utils::{self, FromBatch, InputError};
 
 #[derive(Debug)]
 struct Questions {
@@ -16,16 +19,16 @@ struct Questions {
 
 #[derive(Error, Debug, Clone)]
 pub enum DecodeError {
-    #[error("Corrupt questions")]
+    #[error("corrupt answers: {0}")]
     CorruptError(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
-impl Questions {
+impl FromBatch for Questions {
+    type Err = DecodeError;
 
-    fn from_lines(lines: &[&str]) -> Result<Self, DecodeError> {
+    // a group is a line of answers per person
+    fn from_batch(lines: &[&str]) -> Result<Self, Self::Err> {
         let mut qs = BTreeMap::new();
         let mut ls = Vec::new();
         for line in lines {
@@ -40,6 +43,10 @@ impl Questions {
         }
         Ok(Questions{questions: qs, lines: ls})
     }
+}
+
+
+impl Questions {
 
     fn num_q(&self) -> u32 {
         self.questions.len() as u32
@@ -47,14 +54,31 @@ impl Questions {
 }
 
 
-fn process_fn(file_name: &str) -> Vec<Result<Questions, DecodeError>> {
-    let lines = std::fs::read_to_string(file_name)
-        .expect("file not found!");
-    let questions = utils::process_lines_to_batches(lines.lines().collect::<Vec<_>>().as_slice())
-        .iter()
-        .map(|batch| Questions::from_lines(batch))
-        .collect::<Vec<_>>();
-    questions
+impl From<&Questions> for Value {
+    // each person's answers, and how many people answered yes to each question
+    fn from(q: &Questions) -> Self {
+        json::object(vec![
+            ("people", q.lines.clone().into()),
+            ("answers", Value::Object(
+                q.questions.iter().map(|(c, n)| (c.to_string(), (*n).into())).collect())),
+        ])
+    }
+}
+
+
+// the groups, stopping at the first that can't be decoded
+fn parse_groups(input: &str) -> Result<Vec<Questions>, SolveError> {
+    utils::read_batches::<Questions>(input)
+        .map(|(lines, group)| group.map_err(
+            |e| SolveError::ParseError(format!("{}: {}", utils::line_range(&lines), e))))
+        .collect()
+}
+
+
+fn process_fn(file_name: &str) -> Result<Vec<Questions>, InputError> {
+    let lines = utils::read_input(file_name)?;
+    parse_groups(&lines)
+        .map_err(|e| InputError::Invalid { path: file_name.to_string(), error: e.to_string() })
 }
 
 
@@ -63,15 +87,65 @@ fn sum_num_questions(qs: &[&Questions]) -> u32 {
 }
 
 
-pub fn day6_1() {
-    println!("Day 6_1.");
-    let files = ["./input/day6-test-data.txt", "./input/day6.txt"];
-    for file in files.iter() {
-        let qs = process_fn(file);
-        let qsu = qs.iter().map(|q| q.as_ref().unwrap()).collect::<Vec<_>>();
+// only the parser: every line of a group should be one person's answers.  The groups are split
+// on blank lines just as the solvers split them, and each line in them is checked.
+pub fn check(input: &str) -> Checked {
+    let lines = input.lines().collect::<Vec<_>>();
+    let groups = utils::read_batches::<Questions>(input).collect::<Vec<_>>();
+    let checked = groups.iter().map(|(range, _)| range.clone().count()).sum();
+    let bad = groups
+        .iter()
+        .filter(|(_, group)| group.is_err())
+        .flat_map(|(range, _)| range.clone())
+        .filter_map(|n| Questions::from_batch(&[lines[n - 1]]).err().map(
+            |e| BadLine { line: n, text: lines[n - 1].to_string(), error: e.to_string() }))
+        .collect();
+    Checked { checked, bad }
+}
+
+
+// the groups and their answers
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    let groups = parse_groups(input)?;
+    Ok(groups.iter().collect::<Vec<_>>().into())
+}
+
+
+// how big the groups are, and which questions are answered
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let groups = parse_groups(input)?;
+    let questions = groups.iter().flat_map(|g| g.questions.keys()).collect::<BTreeSet<_>>();
+    Ok(json::object(vec![
+        ("groups", groups.len().into()),
+        ("people", groups.iter().map(|g| g.lines.len()).sum::<usize>().into()),
+        ("group sizes", utils::histogram(groups.iter().map(|g| g.lines.len()))),
+        ("questions answered", questions.into_iter().collect::<String>().into()),
+    ]))
+}
+
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let groups = parse_groups(input)?;
+    Ok(Solution::new(sum_num_questions(&groups.iter().collect::<Vec<_>>())))
+}
+
+
+pub fn day6_1(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 6_1.");
+    let example = parse_groups(puzzles::example(6, "answers").unwrap()).unwrap();
+    let input = process_fn("./input/day6.txt")?;
+    for (name, qs, for_input) in [("the example", example, false),
+                                  ("./input/day6.txt", input, true)] {
+        let qsu = qs.iter().collect::<Vec<_>>();
         for q in &qsu {
-            println!("{:?}", q);
+            say!(ctx, "{:?}", q);
+        }
+        let sum = sum_num_questions(qsu.as_slice());
+        if for_input {
+            ctx.answer_for_input(name, sum);
+        } else {
+            ctx.answer(name, sum);
         }
-        println!("Sum of questions: {}", sum_num_questions(qsu.as_slice()));
     }
+    Ok(())
 }
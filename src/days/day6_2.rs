@@ -1,11 +1,13 @@
-use std::str::FromStr;
-use std::fmt;
 use std::collections::BTreeMap;
 
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::registry::{SolveError, Solution};
+use crate::puzzles;
+use crate::say;
+use crate::utils::{self, FromBatch, InputError};
 
 #[derive(Debug)]
 struct Questions {
@@ -16,16 +18,16 @@ struct Questions {
 
 #[derive(Error, Debug, Clone)]
 pub enum DecodeError {
-    #[error("Corrupt questions")]
+    #[error("corrupt answers: {0}")]
     CorruptError(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
-impl Questions {
+impl FromBatch for Questions {
+    type Err = DecodeError;
 
-    fn from_lines(lines: &[&str]) -> Result<Self, DecodeError> {
+    // a group is a line of answers per person
+    fn from_batch(lines: &[&str]) -> Result<Self, Self::Err> {
         let mut qs = BTreeMap::new();
         let mut ls = Vec::new();
         for line in lines {
@@ -40,6 +42,10 @@ impl Questions {
         }
         Ok(Questions{questions: qs, lines: ls})
     }
+}
+
+
+impl Questions {
 
     // calculate the number of questions where the sum is > 1
     fn num_q(&self) -> u32 {
@@ -49,14 +55,19 @@ impl Questions {
 }
 
 
-fn process_fn(file_name: &str) -> Vec<Result<Questions, DecodeError>> {
-    let lines = std::fs::read_to_string(file_name)
-        .expect("file not found!");
-    let questions = utils::process_lines_to_batches(lines.lines().collect::<Vec<_>>().as_slice())
-        .iter()
-        .map(|batch| Questions::from_lines(batch))
-        .collect::<Vec<_>>();
-    questions
+// the groups, stopping at the first that can't be decoded
+fn parse_groups(input: &str) -> Result<Vec<Questions>, SolveError> {
+    utils::read_batches::<Questions>(input)
+        .map(|(lines, group)| group.map_err(
+            |e| SolveError::ParseError(format!("{}: {}", utils::line_range(&lines), e))))
+        .collect()
+}
+
+
+fn process_fn(file_name: &str) -> Result<Vec<Questions>, InputError> {
+    let lines = utils::read_input(file_name)?;
+    parse_groups(&lines)
+        .map_err(|e| InputError::Invalid { path: file_name.to_string(), error: e.to_string() })
 }
 
 
@@ -65,15 +76,28 @@ fn sum_num_questions(qs: &[&Questions]) -> u32 {
 }
 
 
-pub fn day6_2() {
-    println!("Day 6_2.");
-    let files = ["./input/day6-test-data.txt", "./input/day6.txt"];
-    for file in files.iter() {
-        let qs = process_fn(file);
-        let qsu = qs.iter().map(|q| q.as_ref().unwrap()).collect::<Vec<_>>();
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let groups = parse_groups(input)?;
+    Ok(Solution::new(sum_num_questions(&groups.iter().collect::<Vec<_>>())))
+}
+
+
+pub fn day6_2(ctx: &Context) -> Result<(), InputError> {
+    say!(ctx, "Day 6_2.");
+    let example = parse_groups(puzzles::example(6, "answers").unwrap()).unwrap();
+    let input = process_fn("./input/day6.txt")?;
+    for (name, qs, for_input) in [("the example", example, false),
+                                  ("./input/day6.txt", input, true)] {
+        let qsu = qs.iter().collect::<Vec<_>>();
         //for q in &qsu {
-            //println!("{:?}", q);
+            //say!(ctx, "{:?}", q);
         //}
-        println!("Sum of questions answered by all passengers in group: {}", sum_num_questions(qsu.as_slice()));
+        let sum = sum_num_questions(qsu.as_slice());
+        if for_input {
+            ctx.answer_for_input(name, sum);
+        } else {
+            ctx.answer(name, sum);
+        }
     }
+    Ok(())
 }
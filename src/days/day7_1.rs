@@ -1,5 +1,4 @@
 use std::str::FromStr;
-use std::fmt;
 use std::collections::{
     HashMap,
     HashSet,
@@ -8,7 +7,11 @@ use std::collections::{
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::registry::{Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 #[derive(Debug)]
 struct Rule {
@@ -20,10 +23,8 @@ type ContainsMap = HashMap::<String, HashSet<String>>;
 
 #[derive(Error, Debug, Clone)]
 pub enum DecodeError {
-    #[error("Corrupt questions")]
+    #[error("corrupt rule: {0}")]
     CorruptError(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
@@ -36,7 +37,10 @@ impl FromStr for Rule {
         if parts.len() != 2 {
             return Err(DecodeError::CorruptError(format!("line is malformed: {}", s)));
         }
-        let bag_bits = parts[0].trim().split(" ").collect::<Vec<_>>();
+        let bag_bits = parts[0].trim().split(' ').collect::<Vec<_>>();
+        if bag_bits.len() < 2 {
+            return Err(DecodeError::CorruptError(format!("no bag colour in: {}", s)));
+        }
         let bag = format!("{} {}", bag_bits[0], bag_bits[1]);
         // split the remaining line into bags via the ','
         let chunks = parts[1].split(',').collect::<Vec<_>>();
@@ -46,11 +50,12 @@ impl FromStr for Rule {
                 break;
             }
             // otherwise the line is  <n> bag type <ignored>
-            let bits = chunk.trim().split(" ").collect::<Vec<_>>();
+            let bits = chunk.trim().split(' ').collect::<Vec<_>>();
             if bits.len() != 4 {
                 return Err(DecodeError::CorruptError(format!("line {} has wrong num chunks", s)));
             }
-            let num = bits[0].parse::<u32>().unwrap();
+            let num = bits[0].parse::<u32>().map_err(
+                |e| DecodeError::CorruptError(format!("bad count '{}': {}", bits[0], e)))?;
             let rule = format!("{} {}", bits[1], bits[2]);
             contains.push((num, rule));
         }
@@ -60,11 +65,22 @@ impl FromStr for Rule {
 }
 
 
+impl From<&Rule> for Value {
+    fn from(rule: &Rule) -> Self {
+        let contains = rule.contains
+            .iter()
+            .map(|(count, bag)| json::object(vec![("count", (*count).into()), ("bag", bag.as_str().into())]))
+            .collect::<Vec<_>>();
+        json::object(vec![("bag", rule.bag.as_str().into()), ("contains", contains.into())])
+    }
+}
+
+
 fn containedby_map(rules: &[&Rule]) -> ContainsMap {
-    let mut map = HashMap::new();
+    let mut map = ContainsMap::new();
     for rule in rules.iter() {
         for bag in rule.contains.iter() {
-            map.entry(bag.1.clone()).or_insert(HashSet::new()).insert(rule.bag.clone());
+            map.entry(bag.1.clone()).or_default().insert(rule.bag.clone());
         }
     }
     map
@@ -86,20 +102,86 @@ fn held_by(map: &ContainsMap, bag: &str) -> HashSet<String> {
 }
 
 
-pub fn day7_1() {
+// only the parser: every line should be a rule
+pub fn check(input: &str) -> Checked {
+    utils::check_lines::<Rule>(input)
+}
+
+
+// the bag rules: each bag and the bags it must contain
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    let rules = utils::parse_lines::<Rule>(input)?;
+    Ok(rules.iter().collect::<Vec<_>>().into())
+}
+
+
+// the deepest nesting of bags inside a bag (0 for one that holds nothing); None if a bag ends up
+// inside itself.
+fn depth<'a>(
+    bag: &'a str,
+    rules: &HashMap<&'a str, &'a Rule>,
+    depths: &mut HashMap<&'a str, Option<usize>>,
+) -> Option<usize> {
+    if let Some(d) = depths.get(bag) {
+        return *d;
+    }
+    // mark it while it's being worked out, so a cycle comes back as None
+    depths.insert(bag, None);
+    let mut deepest = Some(0);
+    if let Some(rule) = rules.get(bag) {
+        for (_, inner) in rule.contains.iter() {
+            deepest = match (deepest, depth(inner, rules, depths)) {
+                (Some(d), Some(inner_depth)) => Some(d.max(inner_depth + 1)),
+                _ => None,
+            };
+        }
+    }
+    depths.insert(bag, deepest);
+    deepest
+}
+
+
+// the size and shape of the graph of bags
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let rules = utils::parse_lines::<Rule>(input)?;
+    let by_bag = rules.iter().map(|r| (r.bag.as_str(), r)).collect::<HashMap<_, _>>();
+    let mut bags = rules.iter().map(|r| r.bag.as_str()).collect::<HashSet<_>>();
+    bags.extend(rules.iter().flat_map(|r| r.contains.iter().map(|(_, b)| b.as_str())));
+    let mut depths = HashMap::new();
+    let deepest = bags.iter().map(|b| depth(b, &by_bag, &mut depths)).collect::<Option<Vec<_>>>();
+    Ok(json::object(vec![
+        ("rules", rules.len().into()),
+        ("bags", bags.len().into()),
+        ("edges", rules.iter().map(|r| r.contains.len()).sum::<usize>().into()),
+        ("empty bags", rules.iter().filter(|r| r.contains.is_empty()).count().into()),
+        ("deepest nesting", deepest.as_ref().and_then(|d| d.iter().max().copied()).into()),
+        ("has a cycle", deepest.is_none().into()),
+        ("shiny gold nesting", depth("shiny gold", &by_bag, &mut depths).into()),
+    ]))
+}
+
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let rules = utils::parse_lines::<Rule>(input)?;
+    let map = containedby_map(&rules.iter().collect::<Vec<_>>());
+    Ok(Solution::new(held_by(&map, "shiny gold").len()))
+}
+
+
+pub fn day7_1(ctx: &Context) -> Result<(), InputError> {
     // let's grab the test file
     //let test_rules = utils::read_file::<Rule>("./input/day7-test-data.txt");
-    let test_rules = utils::read_file::<Rule>("./input/day7.txt");
-    let rules = test_rules.iter().map(|v| v.as_ref().unwrap()).collect::<Vec<_>>();
+    let test_rules = utils::read_file::<Rule>("./input/day7.txt")?;
+    let rules = test_rules.iter().collect::<Vec<_>>();
     for r in &rules {
-        println!("{:?}", r);
+        say!(ctx, "{:?}", r);
     }
-    println!("contained map:");
+    say!(ctx, "contained map:");
     let map = containedby_map(rules.as_slice());
-    println!("{:?}", map);
-    println!("see what is contained by 'shiny gold'");
+    say!(ctx, "{:?}", map);
+    say!(ctx, "see what is contained by 'shiny gold'");
     let bags = held_by(&map, "shiny gold");
-    println!("{:?}", bags);
-    println!("length: {}", bags.len());
-
+    say!(ctx, "{:?}", bags);
+    ctx.answer_for_input("length", bags.len());
+    Ok(())
 }
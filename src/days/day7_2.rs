@@ -1,14 +1,13 @@
 use std::str::FromStr;
-use std::fmt;
-use std::collections::{
-    HashMap,
-    HashSet,
-};
+use std::collections::HashMap;
 
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::registry::{SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 #[derive(Debug)]
 struct Rule {
@@ -21,10 +20,8 @@ type RulesMap = HashMap::<String, Vec<(u32, String)>>;
 
 #[derive(Error, Debug, Clone)]
 pub enum DecodeError {
-    #[error("Corrupt questions")]
+    #[error("corrupt rule: {0}")]
     CorruptError(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
@@ -37,7 +34,10 @@ impl FromStr for Rule {
         if parts.len() != 2 {
             return Err(DecodeError::CorruptError(format!("line is malformed: {}", s)));
         }
-        let bag_bits = parts[0].trim().split(" ").collect::<Vec<_>>();
+        let bag_bits = parts[0].trim().split(' ').collect::<Vec<_>>();
+        if bag_bits.len() < 2 {
+            return Err(DecodeError::CorruptError(format!("no bag colour in: {}", s)));
+        }
         let bag = format!("{} {}", bag_bits[0], bag_bits[1]);
         // split the remaining line into bags via the ','
         let chunks = parts[1].split(',').collect::<Vec<_>>();
@@ -47,11 +47,12 @@ impl FromStr for Rule {
                 break;
             }
             // otherwise the line is  <n> bag type <ignored>
-            let bits = chunk.trim().split(" ").collect::<Vec<_>>();
+            let bits = chunk.trim().split(' ').collect::<Vec<_>>();
             if bits.len() != 4 {
                 return Err(DecodeError::CorruptError(format!("line {} has wrong num chunks", s)));
             }
-            let num = bits[0].parse::<u32>().unwrap();
+            let num = bits[0].parse::<u32>().map_err(
+                |e| DecodeError::CorruptError(format!("bad count '{}': {}", bits[0], e)))?;
             let rule = format!("{} {}", bits[1], bits[2]);
             contains.push((num, rule));
         }
@@ -85,19 +86,26 @@ fn num_contains(map: &RulesMap, bag: &str) -> u32 {
     count
 }
 
-pub fn day7_2() {
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let rules = utils::parse_lines::<Rule>(input)?;
+    let map = rules_map(&rules.iter().collect::<Vec<_>>());
+    Ok(Solution::new(num_contains(&map, "shiny gold")))
+}
+
+
+pub fn day7_2(ctx: &Context) -> Result<(), InputError> {
     // let's grab the test file
     //let test_rules = utils::read_file::<Rule>("./input/day7-test-data.txt");
-    let test_rules = utils::read_file::<Rule>("./input/day7.txt");
-    let rules = test_rules.iter().map(|v| v.as_ref().unwrap()).collect::<Vec<_>>();
+    let test_rules = utils::read_file::<Rule>("./input/day7.txt")?;
+    let rules = test_rules.iter().collect::<Vec<_>>();
     for r in &rules {
-        println!("{:?}", r);
+        say!(ctx, "{:?}", r);
     }
-    println!("rules map:");
+    say!(ctx, "rules map:");
     let map = rules_map(rules.as_slice());
-    println!("{:?}", map);
-    println!("see number of bags in a 'shiny gold'");
+    say!(ctx, "{:?}", map);
     let bags = num_contains(&map, "shiny gold");
-    println!("{}", bags);
-
+    ctx.answer_for_input("number of bags in a 'shiny gold'", bags);
+    Ok(())
 }
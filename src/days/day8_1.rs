@@ -1,22 +1,23 @@
+use std::convert::TryFrom;
 use std::str::FromStr;
-use std::fmt;
-use std::collections::{
-    HashMap,
-    HashSet,
-};
+use std::collections::HashSet;
 
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::registry::{Checked, SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Debug, PartialEq)]
 enum OpCodeType {
-    ACC,
-    JMP,
-    NOP,
-    UNKNOWN,
+    Acc,
+    Jmp,
+    Nop,
+    Unknown,
 }
 
 
@@ -28,23 +29,21 @@ struct OpCode {
 
 #[derive(Error, Debug, Clone)]
 pub enum DecodeError {
-    #[error("Corrupt questions")]
+    #[error("corrupt instruction: {0}")]
     CorruptError(String),
-    #[error("Invalid Opcode")]
+    #[error("invalid opcode: {0}")]
     InvalidOpCode(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
 fn code_to_opcodetype(s: &str) -> Result<OpCodeType, DecodeError> {
     let res = match s {
-        "acc" => OpCodeType::ACC,
-        "jmp" => OpCodeType::JMP,
-        "nop" => OpCodeType::NOP,
-        _     => OpCodeType::UNKNOWN,
+        "acc" => OpCodeType::Acc,
+        "jmp" => OpCodeType::Jmp,
+        "nop" => OpCodeType::Nop,
+        _     => OpCodeType::Unknown,
     };
-    if res == OpCodeType::UNKNOWN {
+    if res == OpCodeType::Unknown {
         return Err(DecodeError::InvalidOpCode(s.to_string()));
     }
     Ok(res)
@@ -56,7 +55,7 @@ impl FromStr for OpCode {
 
     // parses a '1-3 c' into a Rules of bounds (1,3) and element 'c'
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(" ").collect::<Vec<_>>();
+        let parts = s.split(' ').collect::<Vec<_>>();
         if parts.len() != 2 {
             return Err(DecodeError::CorruptError(format!("line is malformed: {}", s)));
         }
@@ -67,45 +66,105 @@ impl FromStr for OpCode {
 }
 
 
+impl From<&OpCode> for Value {
+    fn from(opcode: &OpCode) -> Self {
+        let code = match opcode.code {
+            OpCodeType::Acc => "acc",
+            OpCodeType::Jmp => "jmp",
+            OpCodeType::Nop => "nop",
+            OpCodeType::Unknown => "unknown",
+        };
+        json::object(vec![("op", code.into()), ("argument", opcode.argument.into())])
+    }
+}
+
+
 // run the opcodes from 0 until we loop.  keep a record of what we have hit using a hashset.
-// use the acc and pc (program counter) to run the machine until it loops.
-fn run_til_loop(opcodes: &[&OpCode]) -> i32 {
+// use the acc and pc (program counter) to run the machine until it loops.  None if it runs off
+// the program (or jumps outside it) instead.
+fn run_til_loop(opcodes: &[&OpCode]) -> Option<i32> {
     let mut acc = 0;
     let mut pc: usize = 0;
     let mut visited = HashSet::new();
     loop {
         if visited.contains(&pc) {
-            return acc;
+            return Some(acc);
         }
         visited.insert(pc);
-        let opcode = &opcodes[pc];
+        let opcode = opcodes.get(pc)?;
         match opcode.code {
-            OpCodeType::ACC => {
+            OpCodeType::Acc => {
                 acc += opcode.argument;
                 pc += 1;
             },
-            OpCodeType::JMP => {
-                pc = ((pc as i32) + opcode.argument) as usize;
+            OpCodeType::Jmp => {
+                pc = usize::try_from(pc as i64 + i64::from(opcode.argument)).ok()?;
             },
-            OpCodeType::NOP => {
+            OpCodeType::Nop => {
                 pc += 1;
             },
-            OpCodeType::UNKNOWN => {
+            OpCodeType::Unknown => {
                 panic!("Shouldn't be able to get here!");
             },
         }
     }
 }
 
-pub fn day8_1() {
+
+// only the parser: every line should be an instruction
+pub fn check(input: &str) -> Checked {
+    utils::check_lines::<OpCode>(input)
+}
+
+
+// the program, one instruction per line
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    let opcodes = utils::parse_lines::<OpCode>(input)?;
+    Ok(opcodes.iter().collect::<Vec<_>>().into())
+}
+
+
+// the mix of instructions, and where the jumps go
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let opcodes = utils::parse_lines::<OpCode>(input)?;
+    let jumps = opcodes
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| op.code == OpCodeType::Jmp)
+        .map(|(pc, op)| (op.argument, pc as i32 + op.argument))
+        .collect::<Vec<_>>();
+    let outside = jumps.iter().filter(|(_, to)| *to < 0 || *to > opcodes.len() as i32).count();
+    let ops = opcodes.iter().map(|op| format!("{:?}", op.code).to_lowercase());
+    Ok(json::object(vec![
+        ("instructions", opcodes.len().into()),
+        ("operations", utils::histogram(ops)),
+        ("shortest jump", jumps.iter().map(|(by, _)| *by).min().into()),
+        ("longest jump", jumps.iter().map(|(by, _)| *by).max().into()),
+        ("jumps out of the program", outside.into()),
+    ]))
+}
+
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let opcodes = utils::parse_lines::<OpCode>(input)?;
+    run_til_loop(&opcodes.iter().collect::<Vec<_>>())
+        .map(Solution::new)
+        .ok_or_else(|| SolveError::NoAnswer("the program never loops".to_string()))
+}
+
+
+pub fn day8_1(ctx: &Context) -> Result<(), InputError> {
     // let's grab the test file
     //let opcode_results = utils::read_file::<OpCode>("./input/day8-test-data.txt");
-    let opcode_results = utils::read_file::<OpCode>("./input/day8.txt");
-    let opcodes = opcode_results.iter().map(|v| v.as_ref().unwrap()).collect::<Vec<_>>();
+    let opcode_results = utils::read_file::<OpCode>("./input/day8.txt")?;
+    let opcodes = opcode_results.iter().collect::<Vec<_>>();
     for r in &opcodes {
-        println!("{:?}", r);
+        say!(ctx, "{:?}", r);
+    }
+    match run_til_loop(&opcodes) {
+        Some(acc) => ctx.answer_for_input("Acc just before repeat", acc),
+        None => say!(ctx, "The program never loops"),
     }
-    let acc = run_til_loop(&opcodes);
-    println!("Acc just before repeat is: {}", acc);
+    Ok(())
 }
 
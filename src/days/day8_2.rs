@@ -1,3 +1,4 @@
+use std::convert::TryFrom;
 use std::str::FromStr;
 use std::fmt;
 use std::collections::{
@@ -8,15 +9,19 @@ use std::collections::{
 
 use thiserror::Error;
 
-use crate::utils;
+use crate::context::Context;
+use crate::progress::Progress;
+use crate::registry::{SolveError, Solution};
+use crate::say;
+use crate::utils::{self, InputError};
 
 
 #[derive(Debug, PartialEq,Clone)]
 enum OpCodeType {
-    ACC,
-    JMP,
-    NOP,
-    UNKNOWN,
+    Acc,
+    Jmp,
+    Nop,
+    Unknown,
 }
 
 
@@ -26,25 +31,36 @@ struct OpCode {
     argument: i32,
 }
 
+impl fmt::Display for OpCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let code = match self.code {
+            OpCodeType::Acc     => "acc",
+            OpCodeType::Jmp     => "jmp",
+            OpCodeType::Nop     => "nop",
+            OpCodeType::Unknown => "???",
+        };
+        write!(f, "{} {:+}", code, self.argument)
+    }
+}
+
+
 #[derive(Error, Debug, Clone)]
 pub enum DecodeError {
-    #[error("Corrupt questions")]
+    #[error("corrupt instruction: {0}")]
     CorruptError(String),
-    #[error("Invalid Opcode")]
+    #[error("invalid opcode: {0}")]
     InvalidOpCode(String),
-    #[error("unknown passport error")]
-    Unknown,
 }
 
 
 fn code_to_opcodetype(s: &str) -> Result<OpCodeType, DecodeError> {
     let res = match s {
-        "acc" => OpCodeType::ACC,
-        "jmp" => OpCodeType::JMP,
-        "nop" => OpCodeType::NOP,
-        _     => OpCodeType::UNKNOWN,
+        "acc" => OpCodeType::Acc,
+        "jmp" => OpCodeType::Jmp,
+        "nop" => OpCodeType::Nop,
+        _     => OpCodeType::Unknown,
     };
-    if res == OpCodeType::UNKNOWN {
+    if res == OpCodeType::Unknown {
         return Err(DecodeError::InvalidOpCode(s.to_string()));
     }
     Ok(res)
@@ -56,7 +72,7 @@ impl FromStr for OpCode {
 
     // parses a '1-3 c' into a Rules of bounds (1,3) and element 'c'
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(" ").collect::<Vec<_>>();
+        let parts = s.split(' ').collect::<Vec<_>>();
         if parts.len() != 2 {
             return Err(DecodeError::CorruptError(format!("line is malformed: {}", s)));
         }
@@ -68,7 +84,8 @@ impl FromStr for OpCode {
 
 
 // run the opcodes from 0 until we loop.  keep a record of what we have hit using a hashset.
-// use the acc and pc (program counter) to run the machine until it loops.
+// use the acc and pc (program counter) to run the machine until it loops, or jumps somewhere
+// other than the end of the program, which is as much a failure as looping.
 fn run_til_complete_or_loop(opcodes: &[&OpCode]) -> Result<i32, i32> {
     let mut acc = 0;
     let mut pc: usize = 0;
@@ -78,72 +95,151 @@ fn run_til_complete_or_loop(opcodes: &[&OpCode]) -> Result<i32, i32> {
         if pc == last {
             return Ok(acc);
         }
-        if visited.contains(&pc) {
+        if pc > last || visited.contains(&pc) {
             return Err(acc);
         }
         visited.insert(pc);
         let opcode = &opcodes[pc];
         match opcode.code {
-            OpCodeType::ACC => {
+            OpCodeType::Acc => {
                 acc += opcode.argument;
-                pc += 1;
-            },
-            OpCodeType::JMP => {
-                pc = ((pc as i32) + opcode.argument) as usize;
-            },
-            OpCodeType::NOP => {
-                pc += 1;
             },
-            OpCodeType::UNKNOWN => {
+            OpCodeType::Unknown => {
                 panic!("Shouldn't be able to get here!");
             },
+            _ => {},
         }
+        pc = next_pc(opcode, pc);
     }
 }
 
 
 // create multiple versions of the opcodes where the jmp or nop is reversed and that it gets to an
-// Ok.
-fn find_broken_opcode(opcodes: &[&OpCode]) -> i32 {
-    let mut pos: usize = 0;
+// Ok.  Returns the position of the broken opcode and the acc, or None if no patch works.
+fn find_broken_opcode(opcodes: &[&OpCode], progress: &Progress) -> Option<(usize, i32)> {
     let mut new_code: OpCode;
-    loop {
-        let opcode = &opcodes[pos];
+    progress.set_total(opcodes.len() as u64);
+    for (pos, opcode) in opcodes.iter().enumerate() {
         new_code = match opcode.code {
-            OpCodeType::JMP => OpCode {code: OpCodeType::NOP, argument: opcode.argument },
-            OpCodeType::NOP => OpCode {code: OpCodeType::JMP, argument: opcode.argument },
-            _               => OpCode {code: OpCodeType::UNKNOWN, argument: opcode.argument },
+            OpCodeType::Jmp => OpCode {code: OpCodeType::Nop, argument: opcode.argument },
+            OpCodeType::Nop => OpCode {code: OpCodeType::Jmp, argument: opcode.argument },
+            _               => OpCode {code: OpCodeType::Unknown, argument: opcode.argument },
         };
-        if new_code.code != OpCodeType::UNKNOWN {
+        if new_code.code != OpCodeType::Unknown {
             if let Ok(acc) = use_new_opcode(opcodes, &new_code, pos) {
-                return acc;
+                return Some((pos, acc));
             }
         }
-        pos += 1;
+        progress.inc();
     }
+    None
 }
 
 
 // this is inefficient as it copies the whole lot to make one change; but I gave up fighting the
 // borrow checker fo this one.
 fn use_new_opcode<'a>(opcodes: &'a [&'a OpCode], new_opcode: &'a OpCode, pos: usize) -> Result<i32, i32> {
-    let mut copy = opcodes.iter().cloned().collect::<Vec<_>>();
+    let mut copy = opcodes.to_vec();
     copy[pos] = new_opcode;
-    let res = run_til_complete_or_loop(copy.as_slice());
-    res
+    run_til_complete_or_loop(copy.as_slice())
+}
+
+
+// the position of the instruction that runs after the one at pc, if it isn't patched.  A jump to
+// before the start of the program is usize::MAX, which is past its end too.
+fn next_pc(opcode: &OpCode, pc: usize) -> usize {
+    match opcode.code {
+        OpCodeType::Jmp => usize::try_from(pc as i64 + i64::from(opcode.argument))
+            .unwrap_or(usize::MAX),
+        _               => pc + 1,
+    }
+}
+
+
+// a jmp becomes a nop and vice versa; nothing else can be patched.
+fn flip_opcode(opcode: &OpCode) -> Option<OpCode> {
+    match opcode.code {
+        OpCodeType::Jmp => Some(OpCode {code: OpCodeType::Nop, argument: opcode.argument }),
+        OpCodeType::Nop => Some(OpCode {code: OpCodeType::Jmp, argument: opcode.argument }),
+        _               => None,
+    }
+}
+
+
+// rather than trying every patch in turn, work backwards from the end of the program to find all
+// the instructions that reach it unpatched.  Then walk the (looping) program from the start; the
+// first jmp or nop that, when flipped, lands on one of those instructions is the broken one.
+fn find_broken_opcode_graph(opcodes: &[&OpCode]) -> Option<(usize, i32)> {
+    let last = opcodes.len();
+    let mut comes_from: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (pc, opcode) in opcodes.iter().enumerate() {
+        comes_from.entry(next_pc(opcode, pc)).or_default().push(pc);
+    }
+    let mut reaches_end = HashSet::new();
+    let mut todo = vec![last];
+    while let Some(pc) = todo.pop() {
+        if reaches_end.insert(pc) {
+            if let Some(froms) = comes_from.get(&pc) {
+                todo.extend(froms);
+            }
+        }
+    }
+
+    let mut pc: usize = 0;
+    let mut visited = HashSet::new();
+    while pc < last && visited.insert(pc) {
+        let opcode = opcodes[pc];
+        if let Some(new_code) = flip_opcode(opcode) {
+            if reaches_end.contains(&next_pc(&new_code, pc)) {
+                if let Ok(acc) = use_new_opcode(opcodes, &new_code, pc) {
+                    return Some((pc, acc));
+                }
+            }
+        }
+        pc = next_pc(opcode, pc);
+    }
+    None
 }
 
 
-pub fn day8_2() {
+// the patched instruction is the evidence.
+fn patched_solution(opcodes: &[OpCode], pos: usize, acc: i32) -> Solution {
+    Solution::new(acc)
+        .with("index", pos)
+        .with("was", opcodes[pos].to_string())
+        .with("now", flip_opcode(&opcodes[pos]).map(|o| o.to_string()))
+}
+
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Solution, SolveError> {
+    let opcodes = utils::parse_lines::<OpCode>(input)?;
+    find_broken_opcode(&opcodes.iter().collect::<Vec<_>>(), ctx.progress())
+        .map(|(pos, acc)| patched_solution(&opcodes, pos, acc))
+        .ok_or_else(|| SolveError::NoAnswer("no single patch makes the program finish".to_string()))
+}
+
+
+pub fn solve_graph(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let opcodes = utils::parse_lines::<OpCode>(input)?;
+    find_broken_opcode_graph(&opcodes.iter().collect::<Vec<_>>())
+        .map(|(pos, acc)| patched_solution(&opcodes, pos, acc))
+        .ok_or_else(|| SolveError::NoAnswer("no single patch makes the program finish".to_string()))
+}
+
+
+pub fn day8_2(ctx: &Context) -> Result<(), InputError> {
     // let's grab the test file
     //let opcode_results = utils::read_file::<OpCode>("./input/day8-test-data.txt");
-    let opcode_results = utils::read_file::<OpCode>("./input/day8.txt");
-    let opcodes = opcode_results.iter().map(|v| v.as_ref().unwrap()).collect::<Vec<_>>();
+    let opcode_results = utils::read_file::<OpCode>("./input/day8.txt")?;
+    let opcodes = opcode_results.iter().collect::<Vec<_>>();
     //for r in &opcodes {
-        //println!("{:?}", r);
+        //say!(ctx, "{:?}", r);
     //}
-    let acc = find_broken_opcode(&opcodes);
-    println!("Fixed broken opcode: {:?}", acc);
+    match find_broken_opcode(&opcodes, ctx.progress()) {
+        Some((_, acc)) => ctx.answer_for_input("Fixed broken opcode", acc),
+        None => say!(ctx, "No single patch makes the program finish"),
+    }
+    Ok(())
 }
 
 
@@ -1,79 +1,131 @@
-use std::str::FromStr;
-use std::fmt;
-use std::collections::{
-    HashMap,
-    HashSet,
-};
+use std::collections::VecDeque;
+use std::io::BufRead;
 
+use crate::context::Context;
+use crate::json::{self, Value};
+use crate::progress::Progress;
+use crate::registry::{Checked, SolveError, Solution};
+use crate::utils::{self, InputError};
 
-use thiserror::Error;
 
-use crate::utils;
+// whether two different numbers in previous add up to target
+fn is_pair_sum(previous: &[u64], target: u64) -> bool {
+    previous
+        .iter()
+        .enumerate()
+        .any(|(i, a)| previous[i + 1..].iter().any(|b| a.checked_add(*b) == Some(target)))
+}
 
 
-#[derive(Error, Debug, Clone)]
-pub enum DecodeError {
-    #[error("Corrupt questions")]
-    CorruptError(String),
-    #[error("Invalid Opcode")]
-    InvalidOpCode(String),
-    #[error("unknown passport error")]
-    Unknown,
+// the numbers that aren't a sum of a pair of the `window` numbers before them
+fn invalid_numbers(numbers: &[u64], window: usize) -> impl Iterator<Item = u64> + '_ {
+    (window..numbers.len().max(window))
+        .filter(move |&pos| !is_pair_sum(&numbers[pos - window..pos], numbers[pos]))
+        .map(move |pos| numbers[pos])
 }
 
 
 fn find_invalid(numbers: &[u64], window: usize) -> Option<u64> {
-    if window >= numbers.len() {
-        return None;
+    invalid_numbers(numbers, window).next()
+}
+
+
+// only the parser: every line should be a number
+pub fn check(input: &str) -> Checked {
+    utils::check_lines::<u64>(input)
+}
+
+
+// the XMAS data, as a list of numbers
+pub fn dump(input: &str) -> Result<Value, SolveError> {
+    Ok(utils::parse_lines::<u64>(input)?.into())
+}
+
+
+// the puzzle has more numbers than the preamble of 25, and exactly one of them isn't the sum of a
+// pair before it; the reason an input of numbers can't be for day 9, if it can't.
+pub fn implausible(input: &str) -> Option<String> {
+    let numbers = utils::parse_lines::<u64>(input).ok()?;
+    if numbers.len() <= 25 {
+        return Some(format!("only {} numbers, and the preamble is 25", numbers.len()));
     }
-    'search: for pos in window..numbers.len() {
-        let target = numbers[pos];
-        println!("Testing: {} @{}", target, pos);
-        // now check all the pairs of possible numbers between pos-window and pos-1 for a sum that
-        // adds up to target:
-        for lower in pos-window .. pos-1 {
-            for upper in lower+1 .. pos {
-                println!("for: {}, trying pair {}@{} and {}@{} == {}", target,
-                    numbers[lower], lower, numbers[upper], upper, numbers[lower] + numbers[upper]);
-                if numbers[lower] + numbers[upper] == target {
-                    continue 'search;
-                }
+    match invalid_numbers(&numbers, 25).count() {
+        1 => None,
+        invalid => Some(format!("{} numbers aren't the sum of a pair, not 1", invalid)),
+    }
+}
+
+
+// how many numbers, and their range
+pub fn describe(input: &str) -> Result<Value, SolveError> {
+    let numbers = utils::parse_lines::<u64>(input)?;
+    Ok(json::object(utils::number_stats(&numbers)))
+}
+
+
+pub fn solve_1(input: &str, _ctx: &Context) -> Result<Solution, SolveError> {
+    let numbers = utils::parse_lines::<u64>(input)?;
+    find_invalid(&numbers, 25)
+        .map(Solution::new)
+        .ok_or_else(|| SolveError::NoAnswer("every number is a sum of a previous pair".to_string()))
+}
+
+
+// find_invalid for numbers as they are read, keeping only the last `window` of them.
+fn find_invalid_streaming<I>(numbers: I, window: usize) -> Result<Option<u64>, SolveError>
+    where I: Iterator<Item = Result<u64, utils::InputError>>
+{
+    let mut previous: VecDeque<u64> = VecDeque::with_capacity(window + 1);
+    for number in numbers {
+        let number = number?;
+        if previous.len() == window {
+            if !is_pair_sum(previous.make_contiguous(), number) {
+                return Ok(Some(number));
             }
+            previous.pop_front();
         }
-        // didn't find a sum, so let'd return it:
-        return Some(target);
+        previous.push_back(number);
     }
-    None
+    Ok(None)
+}
+
+
+pub fn solve_1_streaming(source: &str, input: &mut dyn BufRead, _ctx: &Context)
+    -> Result<Solution, SolveError>
+{
+    find_invalid_streaming(utils::stream_lines::<u64, _>(source, input), 25)?
+        .map(Solution::new)
+        .ok_or_else(|| SolveError::NoAnswer("every number is a sum of a previous pair".to_string()))
 }
 
-pub fn day9_1() {
+
+pub fn day9_1(ctx: &Context) -> Result<(), InputError> {
     //let numbers_results = utils::read_file::<u64>("./input/day9-test-data.txt");
-    let numbers_results = utils::read_file::<u64>("./input/day9.txt");
-    let numbers = numbers_results.iter().map(|v| v.clone().unwrap()).collect::<Vec<_>>();
-    //println!("{:?}", numbers);
-    println!("Invalid num: {:?}", find_invalid(&numbers, 25));
+    let numbers = utils::read_file::<u64>("./input/day9.txt")?;
+    //say!(ctx, "{:?}", numbers);
+    ctx.answer_for_input("Invalid num", find_invalid(&numbers, 25));
+    Ok(())
 }
 
 
 
-// find a minimal (early) sequence of numbers that adds up to number, and return the sum of the
-// first and last of tham.
-fn find_sequence_num(numbers: &[u64], number: u64) -> Option<u64> {
+// find a minimal (early) sequence of numbers that adds up to number, and return the positions of
+// the first and last of them.
+fn find_sequence(numbers: &[u64], number: u64, progress: &Progress) -> Option<(usize, usize)> {
     let size = numbers.len();
+    progress.set_total(size as u64);
     'search: for lower in 0..size {
+        progress.inc();
         let mut sum = numbers[lower];
-        for upper in lower+1..=size {
-            sum += numbers[upper];
+        for (upper, n) in numbers.iter().enumerate().skip(lower + 1) {
+            // a sum too big for a u64 has certainly gone too far
+            sum = match sum.checked_add(*n) {
+                Some(sum) => sum,
+                None => continue 'search,
+            };
             // found if sum matches the number
             if sum == number {
-                // find smallest, and largest number in the range lower..=upper and add them.
-                let mut largest: u64 = 0;
-                let mut smallest: u64 = std::u64::MAX;
-                for i in lower..=upper {
-                    if numbers[i] > largest { largest = numbers[i];}
-                    if numbers[i] < smallest { smallest = numbers[i];}
-                }
-                return Some(smallest + largest);
+                return Some((lower, upper));
             }
             // gone too far so try next number in the list
             if sum > number {
@@ -84,14 +136,46 @@ fn find_sequence_num(numbers: &[u64], number: u64) -> Option<u64> {
     None
 }
 
-pub fn day9_2() {
+
+// find the sequence that adds up to number, and return the sum of the smallest and largest
+// numbers in it.
+fn find_sequence_num(numbers: &[u64], number: u64, progress: &Progress) -> Option<u64> {
+    let (lower, upper) = find_sequence(numbers, number, progress)?;
+    let range = &numbers[lower..=upper];
+    Some(range.iter().min()? + range.iter().max()?)
+}
+
+
+pub fn solve_2(input: &str, ctx: &Context) -> Result<Solution, SolveError> {
+    let numbers = utils::parse_lines::<u64>(input)?;
+    let invalid_num = find_invalid(&numbers, 25)
+        .ok_or_else(|| SolveError::NoAnswer("every number is a sum of a previous pair".to_string()))?;
+    let (lower, upper) = find_sequence(&numbers, invalid_num, ctx.progress())
+        .ok_or_else(|| SolveError::NoAnswer(format!("no sequence adds up to {}", invalid_num)))?;
+    let range = &numbers[lower..=upper];
+    let smallest = *range.iter().min().unwrap_or(&0);
+    let largest = *range.iter().max().unwrap_or(&0);
+    Ok(Solution::new(smallest + largest)
+        .with("invalid", invalid_num)
+        .with("range", json::object(vec![
+            ("first", lower.into()),
+            ("last", upper.into()),
+            ("numbers", range.to_vec().into()),
+        ]))
+        .with("smallest", smallest)
+        .with("largest", largest))
+}
+
+
+pub fn day9_2(ctx: &Context) -> Result<(), InputError> {
     //let numbers_results = utils::read_file::<u64>("./input/day9-test-data.txt");
-    let numbers_results = utils::read_file::<u64>("./input/day9.txt");
-    let numbers = numbers_results.iter().map(|v| v.clone().unwrap()).collect::<Vec<_>>();
-    //println!("{:?}", numbers);
+    let numbers = utils::read_file::<u64>("./input/day9.txt")?;
+    //say!(ctx, "{:?}", numbers);
     if let Some(invalid_num) = find_invalid(&numbers, 25) {
-        println!("Invalid num: {}", invalid_num); 
-        println!("Sequence num: {:?}", find_sequence_num(&numbers, invalid_num));
+        ctx.answer("Invalid num", invalid_num);
+        let sequence_num = find_sequence_num(&numbers, invalid_num, ctx.progress());
+        ctx.answer_for_input("Sequence num", sequence_num);
     }
+    Ok(())
 }
 
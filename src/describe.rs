@@ -0,0 +1,39 @@
+// Print statistics about a day's input, to get a feel for it before (or after) solving it.
+//
+// What is reported depends on the day: the range of the numbers for the days that are just
+// numbers, and something about the shape of the data (the spread of the password rules, the
+// density of the trees, the depth of the bag graph, the mix of instructions) for the others.
+
+
+use crate::explain;
+use crate::inputs;
+use crate::json;
+use crate::registry;
+
+
+/// Describe the input for a day (by default the input of its part 1 solver), readably or as JSON.
+pub fn describe(day: u32, input: Option<&str>, as_json: bool) -> Result<(), String> {
+    let entry = registry::day(day).ok_or(format!("Day {} not defined (yet?)", day))?;
+    let file_name = match input {
+        Some(file_name) => file_name,
+        None => registry::default_input(day)
+            .ok_or(format!("Day {} has no part 1 solver to take the input from", day))?,
+    };
+    let contents = inputs::read(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+
+    let stats = (entry.describe)(&contents).map_err(
+        |e| format!("{}: {} (try 'aoc2020 check {}' to see every bad line)", file_name, e, day))?;
+    if as_json {
+        let document = json::object(vec![
+            ("day", day.into()),
+            ("title", entry.title.into()),
+            ("input", file_name.into()),
+            ("stats", stats),
+        ]);
+        println!("{}", document);
+    } else {
+        explain::print_value(&format!("Day {}: {} ({})", day, entry.title, file_name), &stats, 0);
+    }
+    Ok(())
+}
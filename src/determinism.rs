@@ -0,0 +1,120 @@
+// Check that a day-part gives the same answer and output every time it is run.
+//
+// The walkthrough is first run repeatedly in this process, with its output captured; every
+// HashMap and HashSet is seeded differently even within a process, so this is a quick first look.
+// Then each run is a fresh process (a new copy of this binary) so that anything that varies
+// between processes gets a chance to show up too.  The answer comes from
+// 'aoc2020 solve <day>-<part>' and the output from 'aoc2020 <day>-<part>'.  A run that fails is
+// not compared with the others; it stops the check with whatever it said.
+
+
+use std::env;
+use std::process::Command;
+
+use crate::context::{Context, Sink};
+use crate::progress::Progress;
+use crate::registry;
+
+
+// run this binary with args, returning stdout; a run that exits with an error is an error, with
+// its stderr.
+fn run_self(args: &[&str]) -> Result<String, String> {
+    let exe = env::current_exe().map_err(|e| format!("can't find this executable: {}", e))?;
+    let output = Command::new(exe)
+        .args(args)
+        .output()
+        .map_err(|e| format!("couldn't run {:?}: {}", args, e))?;
+    if !output.status.success() {
+        return Err(format!("'aoc2020 {}' failed ({}):\n{}", args.join(" "), output.status,
+                           String::from_utf8_lossy(&output.stderr).trim_end()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+
+// the first line (1-based) where two outputs differ, with the two versions of the line.
+fn first_difference<'a>(a: &'a str, b: &'a str) -> Option<(usize, &'a str, &'a str)> {
+    let mut la = a.lines();
+    let mut lb = b.lines();
+    let mut n = 0;
+    loop {
+        n += 1;
+        match (la.next(), lb.next()) {
+            (None, None) => return None,
+            (x, y) if x == y => continue,
+            (x, y) => return Some((n, x.unwrap_or("<end of output>"), y.unwrap_or("<end of output>"))),
+        }
+    }
+}
+
+
+// long lines (e.g. a printed HashMap) are cut short so the report stays readable.
+fn truncate(line: &str) -> String {
+    const MAX: usize = 120;
+    if line.chars().count() <= MAX {
+        line.to_string()
+    } else {
+        format!("{}...", line.chars().take(MAX).collect::<String>())
+    }
+}
+
+
+// a report of how many distinct values there were and where the first one differs from run 1.
+fn report(what: &str, values: &[String]) -> bool {
+    let mut distinct: Vec<&String> = Vec::new();
+    for v in values {
+        if !distinct.contains(&v) {
+            distinct.push(v);
+        }
+    }
+    if distinct.len() == 1 {
+        println!("{}: identical in all {} runs", what, values.len());
+        return true;
+    }
+    println!("{}: {} different versions in {} runs", what, distinct.len(), values.len());
+    let run = values.iter().position(|v| v != &values[0]).unwrap_or(0);
+    if let Some((line, a, b)) = first_difference(&values[0], &values[run]) {
+        println!("  first difference between run 1 and run {}, at line {}:", run + 1, line);
+        println!("    run 1: {}", truncate(a));
+        println!("    run {}: {}", run + 1, truncate(b));
+    }
+    false
+}
+
+
+/// Run a day-part `runs` times; returns Ok(true) if every run gave the same answer and output.
+pub fn check_determinism(day: u32, part: u32, runs: usize) -> Result<bool, String> {
+    let walkthrough = match registry::walkthrough_for(day, part) {
+        Some(walkthrough) if !registry::solvers_for(day, part).is_empty() => walkthrough,
+        _ => return Err(format!("Day {0}-{1} not defined (yet?)", day, part)),
+    };
+
+    // the output and the answers it reported, from each run in this process
+    let mut captured = Vec::new();
+    let mut reported = Vec::new();
+    for _ in 0..runs.max(2) {
+        let ctx = Context::new(Sink::Capture, Progress::hidden());
+        (walkthrough.run)(&ctx).map_err(|e| e.to_string())?;
+        captured.push(ctx.captured());
+        reported.push(ctx
+            .answers()
+            .iter()
+            .map(|(label, value)| format!("{}: {}\n", label, value))
+            .collect::<String>());
+    }
+    let day_part = format!("{}-{}", day, part);
+    println!("Checked {} {} times in this process", day_part, captured.len());
+    let reported_ok = report("reported answers", &reported);
+    let captured_ok = report("output", &captured);
+
+    let mut answers = Vec::new();
+    let mut outputs = Vec::new();
+    for _ in 0..runs.max(2) {
+        answers.push(run_self(&["solve", &day_part])?);
+        outputs.push(run_self(&[&day_part])?);
+    }
+    println!("Checked {} in {} fresh processes", day_part, answers.len());
+    let answer_ok = report("answer", &answers);
+    let output_ok = report("output", &outputs);
+    Ok(reported_ok && captured_ok && answer_ok && output_ok)
+}
@@ -0,0 +1,44 @@
+// Write a day's parsed input as JSON, for exploring the data elsewhere (e.g. in a notebook)
+// without re-implementing the parsers.
+//
+// The JSON is whatever the day's parser produces: numbers for the days that are just numbers and
+// an object per record (password, passport, seat, group, rule, instruction) for the others.
+
+
+use std::fs;
+
+use crate::inputs;
+use crate::json;
+use crate::registry;
+
+
+/// Parse the input for a day (by default the input of its part 1 solver) and write it as JSON to
+/// the output file, or to stdout.
+pub fn dump(day: u32, input: Option<&str>, output: Option<&str>) -> Result<(), String> {
+    let entry = registry::day(day).ok_or(format!("Day {} not defined (yet?)", day))?;
+    let file_name = match input {
+        Some(file_name) => file_name,
+        None => registry::default_input(day)
+            .ok_or(format!("Day {} has no part 1 solver to take the input from", day))?,
+    };
+    let contents = inputs::read(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+
+    let data = (entry.dump)(&contents).map_err(
+        |e| format!("{}: {} (try 'aoc2020 check {}' to see every bad line)", file_name, e, day))?;
+    let document = json::object(vec![
+        ("year", registry::YEAR.into()),
+        ("day", day.into()),
+        ("title", entry.title.into()),
+        ("input", file_name.into()),
+        ("data", data),
+    ]);
+    match output {
+        Some(path) => fs::write(path, format!("{}\n", document))
+            .map_err(|e| format!("couldn't write {}: {}", path, e)),
+        None => {
+            println!("{}", document);
+            Ok(())
+        },
+    }
+}
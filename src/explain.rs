@@ -0,0 +1,55 @@
+// Show a solution and the evidence behind it, either readably or as JSON.
+
+
+use crate::json::{self, Value};
+use crate::registry::{Solution, Solver};
+
+
+/// Print a value readably under a label, one line per scalar, nesting objects by indenting them.
+pub fn print_value(label: &str, value: &Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(pairs) => {
+            println!("{}{}:", pad, label);
+            for (k, v) in pairs.iter() {
+                print_value(k, v, indent + 2);
+            }
+        },
+        Value::Array(values) if values.iter().any(|v| matches!(v, Value::Object(_) | Value::Array(_))) => {
+            println!("{}{}: ({})", pad, label, values.len());
+            for v in values.iter() {
+                println!("{}  - {}", pad, v);
+            }
+        },
+        v => println!("{}{}: {}", pad, label, v.to_text()),
+    }
+}
+
+
+/// Print the answer and, if explain is set, each piece of evidence on its own line(s).
+pub fn print_readable(solution: &Solution, explain: bool) {
+    println!("{}", solution.answer);
+    if explain {
+        if solution.evidence.is_empty() {
+            println!("  (no explanation for this solver)");
+        }
+        for (label, value) in solution.evidence.iter() {
+            print_value(label, value, 2);
+        }
+    }
+}
+
+
+/// The solution as a JSON object; the evidence is included as "explanation" if explain is set.
+pub fn to_json(solver: &Solver, solution: &Solution, explain: bool) -> Value {
+    let mut pairs = vec![
+        ("day", solver.day.into()),
+        ("part", solver.part.into()),
+        ("solver", solver.name.into()),
+        ("answer", solution.answer.as_str().into()),
+    ];
+    if explain {
+        pairs.push(("explanation", Value::Object(solution.evidence.clone())));
+    }
+    json::object(pairs)
+}
@@ -0,0 +1,169 @@
+// A C API for the solvers.
+//
+// The library is built as a `cdylib` so that the test harnesses in other languages can call the
+// parsers and solvers in-process.  Text comes back through a caller-supplied buffer: on the way
+// in `*out_len` is the size of `out_buf`, on the way out it is the length of the text (not
+// counting the terminating NUL).  If an answer doesn't fit, nothing is written and
+// AOC_BUFFER_TOO_SMALL is returned with `*out_len` set to the length needed; error messages are
+// truncated to fit instead, so that the status isn't lost.
+//
+// The C header, include/aoc2020.h, is generated from this file with `aoc2020 header`.
+
+
+use std::os::raw::c_char;
+use std::panic;
+use std::ptr;
+use std::slice;
+
+use crate::context::Context;
+use crate::registry::{self, SolveError};
+use crate::utils;
+
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_UNKNOWN_PUZZLE: i32 = 1;
+pub const AOC_BAD_ARGUMENT: i32 = 2;
+pub const AOC_PARSE_ERROR: i32 = 3;
+pub const AOC_NO_ANSWER: i32 = 4;
+pub const AOC_PANICKED: i32 = 5;
+pub const AOC_BUFFER_TOO_SMALL: i32 = 6;
+
+
+// (name, value, description) for each status, in the order they go in the header.
+const STATUSES: &[(&str, i32, &str)] = &[
+    ("AOC_OK", AOC_OK, "the answer is in out_buf"),
+    ("AOC_UNKNOWN_PUZZLE", AOC_UNKNOWN_PUZZLE, "no solver for that year, day and part"),
+    ("AOC_BAD_ARGUMENT", AOC_BAD_ARGUMENT, "a NULL pointer, or the input isn't UTF-8"),
+    ("AOC_PARSE_ERROR", AOC_PARSE_ERROR, "the input couldn't be parsed; out_buf has the error"),
+    ("AOC_NO_ANSWER", AOC_NO_ANSWER, "the input parsed but has no answer; out_buf has the error"),
+    ("AOC_PANICKED", AOC_PANICKED, "the solver panicked; out_buf has the panic message"),
+    ("AOC_BUFFER_TOO_SMALL", AOC_BUFFER_TOO_SMALL, "out_buf is too small; *out_len is the length needed"),
+];
+
+
+const PROTOTYPES: &str = "\
+/* Solve a day-part of a year's puzzle with its primary solver.  The input is input_len bytes of
+ * UTF-8 (it needn't be NUL-terminated).  On entry *out_len is the size of out_buf; on return it is
+ * the length of the text written to out_buf, not counting the terminating NUL. */
+int32_t aoc_solve(uint32_t year, uint32_t day, uint32_t part,
+                  const uint8_t *input, size_t input_len,
+                  char *out_buf, size_t *out_len);
+
+/* List the registered solvers, one per line as \"<year> <day> <part> <name>\", primary solver
+ * first for each day-part.  out_buf and out_len work as for aoc_solve. */
+int32_t aoc_list_solvers(char *out_buf, size_t *out_len);
+";
+
+
+/// The C header for this API.
+pub fn header() -> String {
+    let mut header = String::new();
+    header.push_str("/* aoc2020.h - generated by `aoc2020 header`; don't edit by hand. */\n\n");
+    header.push_str("#ifndef AOC2020_H\n#define AOC2020_H\n\n");
+    header.push_str("#include <stddef.h>\n#include <stdint.h>\n\n");
+    header.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+    for (name, value, description) in STATUSES {
+        header.push_str(&format!("/* {} */\n#define {} {}\n", description, name, value));
+    }
+    header.push('\n');
+    header.push_str(PROTOTYPES);
+    header.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* AOC2020_H */\n");
+    header
+}
+
+
+// Copy text and a terminating NUL into out_buf.  If it doesn't fit then either truncate it (for
+// error messages) or write nothing; *out_len is always set to the full length of the text.
+unsafe fn write_out(text: &str, out_buf: *mut c_char, out_len: *mut usize, truncate: bool) -> bool {
+    let capacity = *out_len;
+    *out_len = text.len();
+    let fits = text.len() < capacity;
+    if out_buf.is_null() || capacity == 0 || !(fits || truncate) {
+        return fits && !out_buf.is_null();
+    }
+    let n = text.len().min(capacity - 1);
+    ptr::copy_nonoverlapping(text.as_ptr() as *const c_char, out_buf, n);
+    *out_buf.add(n) = 0;
+    fits
+}
+
+
+// Write an error message and return its status.
+unsafe fn fail(status: i32, message: &str, out_buf: *mut c_char, out_len: *mut usize) -> i32 {
+    write_out(message, out_buf, out_len, true);
+    status
+}
+
+
+/// Solve a day-part with its primary solver and write the answer into `out_buf`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes (it may be NULL if `input_len` is 0),
+/// `out_len` must be a valid pointer, and `out_buf` must point to at least `*out_len` writable
+/// bytes (it may be NULL if `*out_len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_len: *mut usize,
+) -> i32 {
+    if out_len.is_null() {
+        return AOC_BAD_ARGUMENT;
+    }
+    if input.is_null() && input_len > 0 {
+        return fail(AOC_BAD_ARGUMENT, "input is NULL", out_buf, out_len);
+    }
+    let solver = match registry::solvers_for(day, part).first() {
+        Some(solver) if year == registry::YEAR => *solver,
+        _ => {
+            let message = format!("no solver for {} day {} part {}", year, day, part);
+            return fail(AOC_UNKNOWN_PUZZLE, &message, out_buf, out_len);
+        },
+    };
+    let bytes = if input_len == 0 { &[] } else { slice::from_raw_parts(input, input_len) };
+    let input = match std::str::from_utf8(bytes) {
+        Ok(input) => input,
+        Err(e) => return fail(AOC_BAD_ARGUMENT, &format!("input isn't UTF-8: {}", e), out_buf, out_len),
+    };
+    // a panic mustn't unwind into the caller's C code
+    match panic::catch_unwind(|| (solver.solve)(input, &Context::quiet())) {
+        Ok(Ok(solution)) => {
+            if write_out(&solution.answer, out_buf, out_len, false) {
+                AOC_OK
+            } else {
+                AOC_BUFFER_TOO_SMALL
+            }
+        },
+        Ok(Err(e @ SolveError::ParseError(_))) => fail(AOC_PARSE_ERROR, &e.to_string(), out_buf, out_len),
+        Ok(Err(e @ SolveError::NoAnswer(_))) => fail(AOC_NO_ANSWER, &e.to_string(), out_buf, out_len),
+        Err(payload) => fail(AOC_PANICKED, &utils::panic_message(payload), out_buf, out_len),
+    }
+}
+
+
+/// List the registered solvers into `out_buf`, one per line.
+///
+/// # Safety
+///
+/// `out_len` must be a valid pointer and `out_buf` must point to at least `*out_len` writable
+/// bytes (it may be NULL if `*out_len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn aoc_list_solvers(out_buf: *mut c_char, out_len: *mut usize) -> i32 {
+    if out_len.is_null() {
+        return AOC_BAD_ARGUMENT;
+    }
+    let list = registry::SOLVERS
+        .iter()
+        .map(|s| format!("{} {} {} {}\n", registry::YEAR, s.day, s.part, s.name))
+        .collect::<String>();
+    if write_out(&list, out_buf, out_len, false) {
+        AOC_OK
+    } else {
+        AOC_BUFFER_TOO_SMALL
+    }
+}
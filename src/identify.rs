@@ -0,0 +1,193 @@
+// Work out which day an input file is for.
+//
+// Every day's parser is tried on the file, and the days are ranked by the share of lines (or
+// batches, for passports) that parse cleanly.  Several days are just lists of numbers, so when
+// days tie the primary solvers of both parts are run on the file as well: the day whose solvers
+// find answers ranks higher.  If that still leaves a tie, a day number in the file's name (as in
+// 'day6.txt' or 'day10-small-test-data.txt') settles it.  Before the solvers are run, the days
+// whose puzzles promise more about their input than the format of a line (e.g. day 10's adapters
+// are all different) rank below the days whose input it could be, whatever the solvers find.
+// With --auto the answers of the best match are printed, as long as the match is clear.
+
+
+use std::cmp::Ordering;
+use std::panic;
+
+use crate::context::Context;
+use crate::days;
+use crate::inputs;
+use crate::registry;
+use crate::utils;
+
+
+struct Candidate {
+    day: u32,
+    title: &'static str,
+    parsed: usize,
+    checked: usize,
+    // why the input can't be for the day even though it parses, if it can't
+    implausible: Option<String>,
+    // the answer (or error) of the primary solver of each part that has one
+    answers: Vec<(u32, Result<String, String>)>,
+}
+
+
+impl Candidate {
+    // the share of the lines that parse; 0 when there's nothing to parse
+    fn score(&self) -> f64 {
+        if self.checked == 0 {
+            0.0
+        } else {
+            self.parsed as f64 / self.checked as f64
+        }
+    }
+
+    fn solved(&self) -> usize {
+        self.answers.iter().filter(|(_, a)| a.is_ok()).count()
+    }
+
+    fn same_rank(&self, other: &Candidate) -> bool {
+        self.score() == other.score()
+            && self.implausible.is_some() == other.implausible.is_some()
+            && self.solved() == other.solved()
+    }
+}
+
+
+// the day in a file name such as './input/day10-test-data.txt', if there is one
+fn named_day(file_name: &str) -> Option<u32> {
+    let name = file_name.rsplit('/').next().unwrap_or(file_name).to_lowercase();
+    let digits = name.find("day").map(|i| &name[i + 3..])?;
+    let end = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+    digits[..end].parse::<u32>().ok()
+}
+
+
+// what a day's puzzle promises about its input beyond the format of each line; the reason that an
+// input breaks the promise, if it does.
+fn implausible(day: u32, input: &str) -> Option<String> {
+    match day {
+        9 => days::day9::implausible(input),
+        10 => days::day10::implausible(input),
+        _ => None,
+    }
+}
+
+
+// Run the primary solver of each part of a day on the input.
+fn solve_parts(day: u32, input: &str) -> Vec<(u32, Result<String, String>)> {
+    (1..=2)
+        .filter_map(|part| registry::solvers_for(day, part).first().copied())
+        .map(|solver| {
+            let outcome = panic::catch_unwind(|| (solver.solve)(input, &Context::quiet()));
+            let answer = match outcome {
+                Ok(Ok(solution)) => Ok(solution.answer),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(payload) => Err(format!("panicked: {}", utils::panic_message(payload))),
+            };
+            (solver.part, answer)
+        })
+        .collect()
+}
+
+
+// Every day, best match first.  Only days whose parser accepts the whole input are solved; the
+// rest can't be a match however their solvers get on.
+fn rank(input: &str) -> Vec<Candidate> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut candidates = registry::DAYS
+        .iter()
+        .map(|day| {
+            let checked = (day.check)(input);
+            let parsed = checked.checked - checked.bad.len();
+            let (implausible, answers) = if checked.bad.is_empty() && checked.checked > 0 {
+                (implausible(day.day, input), solve_parts(day.day, input))
+            } else {
+                (None, Vec::new())
+            };
+            Candidate {
+                day: day.day,
+                title: day.title,
+                parsed,
+                checked: checked.checked,
+                implausible,
+                answers,
+            }
+        })
+        .collect::<Vec<_>>();
+    panic::set_hook(hook);
+
+    candidates.sort_by(|a, b| {
+        b.score().partial_cmp(&a.score()).unwrap_or(Ordering::Equal)
+            .then(a.implausible.is_some().cmp(&b.implausible.is_some()))
+            .then(b.solved().cmp(&a.solved()))
+            .then(a.day.cmp(&b.day))
+    });
+    candidates
+}
+
+
+/// Rank the days for an input file and, with auto, print the answers for the best match.  Returns
+/// Ok(false) if no day matches clearly.
+pub fn identify(file_name: &str, auto: bool) -> Result<bool, String> {
+    let input = inputs::read(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+    let candidates = rank(&input);
+
+    println!("{:>3}  {:<22} {:>15}  answers", "day", "title", "parsed");
+    for c in candidates.iter().filter(|c| c.parsed > 0) {
+        let answers = if c.answers.is_empty() {
+            "-".to_string()
+        } else {
+            c.answers
+                .iter()
+                .map(|(part, a)| format!("part {}: {}", part, a.as_deref().unwrap_or("none")))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        println!("{:>3}  {:<22} {:>7}/{:<7}  {}", c.day, c.title, c.parsed, c.checked, answers);
+        if let Some(reason) = &c.implausible {
+            println!("{:>3}  {:<22} {:>15}  but not day {}: {}", "", "", "", c.day, reason);
+        }
+    }
+
+    let best = match candidates.first() {
+        Some(best) if best.score() == 1.0 => best,
+        _ => {
+            println!("\nNo day's parser accepts all of {}", file_name);
+            return Ok(false);
+        },
+    };
+    let tied = candidates.iter().filter(|c| c.same_rank(best)).collect::<Vec<_>>();
+    let best = match tied.as_slice() {
+        [best] => {
+            println!("\n{} looks like the input for day {} ({})", file_name, best.day, best.title);
+            best
+        },
+        _ => match tied.iter().find(|c| Some(c.day) == named_day(file_name)) {
+            Some(best) => {
+                println!("\n{} looks like the input for day {} ({}), going by its name",
+                         file_name, best.day, best.title);
+                best
+            },
+            None => {
+                let days = tied.iter().map(|c| c.day.to_string()).collect::<Vec<_>>();
+                println!("\n{} could be for any of days {}", file_name, days.join(", "));
+                return Ok(false);
+            },
+        },
+    };
+
+    if auto {
+        println!();
+        for (part, answer) in &best.answers {
+            match answer {
+                Ok(answer) => println!("{}-{}: {}", best.day, part, answer),
+                Err(e) => println!("{}-{}: {}", best.day, part, e),
+            }
+        }
+        return Ok(best.solved() == best.answers.len());
+    }
+    Ok(true)
+}
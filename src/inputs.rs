@@ -0,0 +1,56 @@
+// Reading the puzzle inputs by name.
+//
+// An input's name is its path from the root of the repo, as the solvers are registered with it,
+// e.g. './input/day8.txt'.  A file on disk is always read if it is there.  With the `embed-inputs`
+// feature the real inputs and the verified answers in input/ are also compiled into the binary and
+// used when the file isn't there, so that the binary works the same from any directory.  (The
+// examples are compiled in anyway, with the puzzles.)
+
+
+use std::fs;
+use std::io;
+
+
+#[cfg(feature = "embed-inputs")]
+macro_rules! embed {
+    ($($file:expr),* $(,)?) => {
+        &[$(($file, include_str!(concat!("../", $file)))),*]
+    };
+}
+
+
+#[cfg(feature = "embed-inputs")]
+const EMBEDDED: &[(&str, &str)] = embed![
+    "input/answers.txt",
+    "input/day1-1.txt",
+    "input/day2-1.txt",
+    "input/day3-1.txt",
+    "input/day4-1.txt",
+    "input/day5-1.txt",
+    "input/day6.txt",
+    "input/day7.txt",
+    "input/day8.txt",
+    "input/day9.txt",
+    "input/day10.txt",
+];
+
+#[cfg(not(feature = "embed-inputs"))]
+const EMBEDDED: &[(&str, &str)] = &[];
+
+
+/// The copy of an input compiled into the binary, if there is one.
+pub fn embedded(name: &str) -> Option<&'static str> {
+    let name = name.trim_start_matches("./");
+    EMBEDDED.iter().find(|(file, _)| *file == name).map(|(_, contents)| *contents)
+}
+
+
+/// Read an input: the file if there is one, otherwise the copy compiled into the binary.
+pub fn read(name: &str) -> io::Result<String> {
+    match fs::read_to_string(name) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            embedded(name).map(|contents| contents.to_string()).ok_or(e)
+        },
+        result => result,
+    }
+}
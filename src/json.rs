@@ -0,0 +1,389 @@
+// A small JSON value type with a writer and a parser.
+//
+// This is just enough JSON for the history and cache files and for reading exported data; objects
+// keep their keys in the order they were written so that the output is stable.  Whole numbers are
+// kept as integers rather than f64, so that answers and timings above 2^53 survive a round trip.
+
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use thiserror::Error;
+
+
+#[derive(Error, Debug, Clone)]
+pub enum JsonError {
+    #[error("unexpected end of JSON")]
+    UnexpectedEnd,
+    #[error("unexpected character '{0}' at {1}")]
+    UnexpectedChar(char, usize),
+    #[error("invalid number at {0}")]
+    BadNumber(usize),
+    #[error("invalid string escape at {0}")]
+    BadEscape(usize),
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+
+impl Value {
+    /// Look up a key in an object; None if this isn't an object or the key isn't there.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(pairs) => pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The value as it is shown to people: a string as it is, without quotes; anything else as
+    /// JSON.
+    pub fn to_text(&self) -> String {
+        match self {
+            Value::String(s) => s.clone(),
+            v => v.to_string(),
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Integer(n) => Some(*n as f64),
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// The value as a u64, if it is a whole number that fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Integer(n) => u64::try_from(*n).ok(),
+            Value::Number(n) if *n >= 0.0 && n.fract() == 0.0 && *n < u64::MAX as f64 => {
+                Some(*n as u64)
+            },
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(pairs) => Some(pairs),
+            _ => None,
+        }
+    }
+}
+
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(n: $t) -> Self {
+                Value::Integer(n as i128)
+            }
+        })*
+    };
+}
+
+from_integer!(u32, u64, usize, i32, i64);
+
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        Value::Number(n)
+    }
+}
+
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(o: Option<T>) -> Self {
+        o.map(|v| v.into()).unwrap_or(Value::Null)
+    }
+}
+
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(v: Vec<T>) -> Self {
+        Value::Array(v.into_iter().map(|v| v.into()).collect())
+    }
+}
+
+
+/// Build an object from (key, value) pairs, e.g. object(vec![("day", 1.into())])
+pub fn object(pairs: Vec<(&str, Value)>) -> Value {
+    Value::Object(pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
+
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+
+impl fmt::Display for Value {
+    // compact JSON, all on one line
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Value::Number(_) => write!(f, "null"),
+            Value::String(s) => write_string(f, s),
+            Value::Array(values) => {
+                write!(f, "[")?;
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                write!(f, "]")
+            },
+            Value::Object(pairs) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, k)?;
+                    write!(f, ":{}", v)?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+
+/// Parse a complete JSON document.
+pub fn parse(s: &str) -> Result<Value, JsonError> {
+    let mut parser = Parser { chars: s.chars().collect(), pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(value),
+        Some(c) => Err(JsonError::UnexpectedChar(c, parser.pos)),
+    }
+}
+
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn next(&mut self) -> Result<char, JsonError> {
+        let c = self.peek().ok_or(JsonError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JsonError> {
+        let c = self.next()?;
+        if c != expected {
+            return Err(JsonError::UnexpectedChar(c, self.pos - 1));
+        }
+        Ok(())
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value, JsonError> {
+        for w in word.chars() {
+            self.expect(w)?;
+        }
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<Value, JsonError> {
+        self.skip_whitespace();
+        match self.peek().ok_or(JsonError::UnexpectedEnd)? {
+            'n' => self.literal("null", Value::Null),
+            't' => self.literal("true", Value::Bool(true)),
+            'f' => self.literal("false", Value::Bool(false)),
+            '"' => Ok(Value::String(self.string()?)),
+            '[' => self.array(),
+            '{' => self.object(),
+            c if c == '-' || c.is_ascii_digit() => self.number(),
+            c => Err(JsonError::UnexpectedChar(c, self.pos)),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, JsonError> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !(c.is_ascii_digit() || "+-.eE".contains(c)) {
+                break;
+            }
+            self.pos += 1;
+        }
+        let text = self.chars[start..self.pos].iter().collect::<String>();
+        // whole numbers are integers unless they are too big even for those
+        if !text.contains(['.', 'e', 'E']) {
+            if let Ok(n) = text.parse::<i128>() {
+                return Ok(Value::Integer(n));
+            }
+        }
+        text.parse::<f64>().map(Value::Number).map_err(|_| JsonError::BadNumber(start))
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.next()? {
+                '"' => return Ok(s),
+                '\\' => {
+                    let at = self.pos;
+                    let c = match self.next()? {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape(at)?,
+                        _ => return Err(JsonError::BadEscape(at)),
+                    };
+                    s.push(c);
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    // the four hex digits of a '\u' escape
+    fn hex4(&mut self, at: usize) -> Result<u32, JsonError> {
+        let hex = (0..4).map(|_| self.next()).collect::<Result<String, _>>()?;
+        match hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            true => u32::from_str_radix(&hex, 16).map_err(|_| JsonError::BadEscape(at)),
+            false => Err(JsonError::BadEscape(at)),
+        }
+    }
+
+    // after '\u': a character, which outside the basic plane is written as a surrogate pair
+    // ('\ud83c\udf84').  A surrogate without its other half becomes U+FFFD.
+    fn unicode_escape(&mut self, at: usize) -> Result<char, JsonError> {
+        let first = self.hex4(at)?;
+        if !(0xd800..0xdc00).contains(&first) {
+            return Ok(std::char::from_u32(first).unwrap_or('\u{fffd}'));
+        }
+        if self.chars.get(self.pos..self.pos + 2) != Some(&['\\', 'u']) {
+            return Ok('\u{fffd}');
+        }
+        self.pos += 2;
+        let second = self.hex4(at)?;
+        if !(0xdc00..0xe000).contains(&second) {
+            // the next escape is a character of its own
+            self.pos -= 6;
+            return Ok('\u{fffd}');
+        }
+        let c = 0x10000 + ((first - 0xd800) << 10) + (second - 0xdc00);
+        Ok(std::char::from_u32(c).unwrap_or('\u{fffd}'))
+    }
+
+    fn array(&mut self) -> Result<Value, JsonError> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => continue,
+                ']' => return Ok(Value::Array(values)),
+                c => return Err(JsonError::UnexpectedChar(c, self.pos - 1)),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, JsonError> {
+        self.expect('{')?;
+        let mut pairs = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Value::Object(pairs));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            pairs.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => continue,
+                '}' => return Ok(Value::Object(pairs)),
+                c => return Err(JsonError::UnexpectedChar(c, self.pos - 1)),
+            }
+        }
+    }
+}
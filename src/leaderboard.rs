@@ -0,0 +1,159 @@
+// Show a private leaderboard from its JSON export (the "API" link on the leaderboard page).
+//
+// Members are ranked by local score, and each member's stars per day are shown next to the days
+// we have solvers for.  Below the table are the completion times for each member and how long
+// they took to get from part 1 to part 2.
+//
+// Older exports have the timestamps and ids as strings and newer ones as numbers, so both are
+// accepted.
+
+
+use std::collections::BTreeMap;
+
+use crate::json::{self, Value};
+use crate::registry;
+use crate::utils;
+
+
+const DAYS: u32 = 25;
+
+
+#[derive(Debug, Clone)]
+struct Member {
+    name: String,
+    local_score: u64,
+    stars: u64,
+    last_star: u64,
+    // day -> (part 1, part 2) completion timestamps
+    completed: BTreeMap<u32, (Option<u64>, Option<u64>)>,
+}
+
+
+// a number that may have been exported as a string
+fn number(value: &Value) -> Option<u64> {
+    value.as_u64().or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+
+fn star_time(day: &Value, part: &str) -> Option<u64> {
+    day.get(part).and_then(|p| p.get("get_star_ts")).and_then(number)
+}
+
+
+impl Member {
+    fn from_json(id: &str, value: &Value) -> Result<Member, String> {
+        let name = match value.get("name").and_then(|n| n.as_str()) {
+            Some(name) => name.to_string(),
+            None => format!("(anonymous user #{})", id),
+        };
+        let mut completed = BTreeMap::new();
+        if let Some(days) = value.get("completion_day_level").and_then(|d| d.as_object()) {
+            for (day, parts) in days.iter() {
+                let day = day.parse::<u32>().map_err(|_| format!("member {}: bad day '{}'", id, day))?;
+                completed.insert(day, (star_time(parts, "1"), star_time(parts, "2")));
+            }
+        }
+        let stars = value.get("stars").and_then(number).unwrap_or_else(|| {
+            completed.values().map(|(p1, p2)| p1.iter().count() as u64 + p2.iter().count() as u64).sum()
+        });
+        Ok(Member {
+            name,
+            local_score: value.get("local_score").and_then(number).unwrap_or(0),
+            stars,
+            last_star: value.get("last_star_ts").and_then(number).unwrap_or(0),
+            completed,
+        })
+    }
+
+    // '*' for both stars on a day, '+' for just the first, '.' for none
+    fn day_marker(&self, day: u32) -> char {
+        match self.completed.get(&day) {
+            Some((Some(_), Some(_))) => '*',
+            Some((Some(_), None)) => '+',
+            _ => '.',
+        }
+    }
+}
+
+
+fn parse(data: &str) -> Result<(String, Vec<Member>), String> {
+    let board = json::parse(data).map_err(|e| e.to_string())?;
+    let event = board.get("event").and_then(|e| e.as_str()).unwrap_or("?").to_string();
+    let members = board.get("members")
+        .and_then(|m| m.as_object())
+        .ok_or("no members in the leaderboard")?
+        .iter()
+        .map(|(id, member)| Member::from_json(id, member))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((event, members))
+}
+
+
+// e.g. "1h 02m 03s", or "4m 28s" under an hour
+fn format_duration(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, (secs / 60) % 60, secs % 60);
+    match (secs / 86400, h) {
+        (d, _) if d > 0 => format!("{}d {:02}h {:02}m", d, h % 24, m),
+        (_, h) if h > 0 => format!("{}h {:02}m {:02}s", h, m, s),
+        _ => format!("{}m {:02}s", m, s),
+    }
+}
+
+
+// the day numbers, written downwards as two rows of digits
+fn day_digits() -> (String, String) {
+    let tens = (1..=DAYS).map(|d| if d < 10 { ' ' } else { (b'0' + (d / 10) as u8) as char });
+    let units = (1..=DAYS).map(|d| (b'0' + (d % 10) as u8) as char);
+    (tens.collect(), units.collect())
+}
+
+
+/// Render the leaderboard exported to file_name.
+pub fn leaderboard(file_name: &str) -> Result<(), String> {
+    let data = std::fs::read_to_string(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+    let (event, mut members) = parse(&data).map_err(|e| format!("{}: {}", file_name, e))?;
+    // ties go to whoever got their last star first
+    members.sort_by(|a, b| {
+        b.local_score.cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.last_star.cmp(&b.last_star))
+            .then(a.name.cmp(&b.name))
+    });
+
+    let width = members.iter().map(|m| m.name.chars().count()).max().unwrap_or(0).max(13);
+    let (tens, units) = day_digits();
+    println!("Private leaderboard for {}: {} member(s)\n", event, members.len());
+    println!("{:>3} {:>6} {:>6}  {:<width$}  {}", "", "", "", "", tens, width = width);
+    println!("{:>3} {:>6} {:>6}  {:<width$}  {}", "#", "score", "stars", "member", units, width = width);
+    for (rank, member) in members.iter().enumerate() {
+        println!("{:>3} {:>6} {:>6}  {:<width$}  {}", rank + 1, member.local_score, member.stars,
+                 member.name, (1..=DAYS).map(|d| member.day_marker(d)).collect::<String>(),
+                 width = width);
+    }
+    // our own status: '*' where both parts have a solver
+    let ours = (1..=DAYS)
+        .map(|d| match (registry::solvers_for(d, 1).is_empty(), registry::solvers_for(d, 2).is_empty()) {
+            (false, false) => '*',
+            (false, true) => '+',
+            _ => '.',
+        })
+        .collect::<String>();
+    println!("{:>3} {:>6} {:>6}  {:<width$}  {}", "", "", "", "(our solvers)", ours, width = width);
+
+    for member in members.iter().filter(|m| !m.completed.is_empty()) {
+        println!("\n{}", member.name);
+        println!("  {:>3}  {:<23}  {:<23}  part 1 -> 2", "day", "part 1", "part 2");
+        for (day, (part1, part2)) in member.completed.iter() {
+            let between = match (part1, part2) {
+                (Some(p1), Some(p2)) => format_duration(p2.saturating_sub(*p1)),
+                _ => "-".to_string(),
+            };
+            println!("  {:>3}  {:<23}  {:<23}  {}", day,
+                     part1.map(utils::format_timestamp).unwrap_or_else(|| "-".to_string()),
+                     part2.map(utils::format_timestamp).unwrap_or_else(|| "-".to_string()),
+                     between);
+        }
+    }
+    Ok(())
+}
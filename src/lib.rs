@@ -0,0 +1,17 @@
+// The solvers as a library.
+//
+// The `aoc2020` binary uses this to run the puzzles; it is also built as a `cdylib` so that other
+// languages can call the solvers through the C API in `ffi` (see `include/aoc2020.h`).
+
+
+pub mod answers;
+pub mod context;
+pub mod days;
+pub mod ffi;
+pub mod inputs;
+pub mod json;
+pub mod progress;
+pub mod puzzles;
+pub mod registry;
+pub mod rejected;
+pub mod utils;
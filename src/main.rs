@@ -1,16 +1,55 @@
 use std::env;
-use std::cmp;
+use std::io::{self, IsTerminal};
 use std::process;
-use std::io::prelude::*;
-use std::fs::OpenOptions;
-use std::fs;
 
-mod days;
-mod utils;
+use aoc2020::{answers, context, days, ffi, inputs, json, progress, puzzles, registry, rejected,
+              utils};
+use context::{Context, Sink};
+use progress::Progress;
 
-struct Config {
-    day: u32,
-    part: u32,
+mod all;
+mod batch;
+mod bench;
+mod cache;
+mod check;
+mod compare;
+mod determinism;
+mod describe;
+mod dump;
+mod explain;
+mod identify;
+mod leaderboard;
+mod multi;
+mod readme;
+mod report;
+mod runner;
+mod server;
+mod show;
+mod stream;
+mod tui;
+
+enum Config {
+    Run { day: u32, part: u32 },
+    Compare { day: u32, part: u32, runs: usize },
+    Report { html: String, runs: usize },
+    Readme { file: String, runs: usize },
+    Bench(bench::BenchOptions),
+    Batch { manifest: String, workers: usize },
+    Solve { day: u32, part: u32, explain: bool, json: bool },
+    Check { day: u32, input: Option<String> },
+    CheckDeterminism { day: u32, part: u32, runs: usize },
+    Describe { day: u32, input: Option<String>, json: bool },
+    Dump { day: u32, input: Option<String>, output: Option<String> },
+    Multi { day: u32, part: u32, inputs: Vec<String>, runs: usize },
+    Serve { port: u16 },
+    Show { day: u32, example: Option<String> },
+    Stream { day: u32, part: u32, input: Option<String> },
+    Reject { day: u32, part: u32, answer: String, hint: rejected::Hint },
+    Header,
+    All { use_cache: bool },
+    Leaderboard { file: String },
+    Identify { file: String, auto: bool },
+    Tui,
 }
 
 
@@ -21,54 +60,266 @@ impl Config {
     fn new(args: &[String]) -> Result<Config, String> {
         let num_args = args.len();
         if num_args == 1 {
-            return Ok(Config {day: 1, part: 1})
+            return Ok(Config::Run {day: 1, part: 1})
         }
         let command = args[1].to_lowercase();
-        let parts = command.split("-").into_iter().collect::<Vec<&str>>();
-        if parts.len() != 2 {
-            return Err(format!("command '{}' isn't a valid day-part", command));
-        }
-        let day: u32 = parts[0].parse().unwrap_or(0);
-        let part: u32 = parts[1].parse().unwrap_or(0);
-        if day < 1 || day > MAX_DAY || part < 1 || part > 2 {
-            return Err(format!("day or part is not parsable as an int or not in range: input was '{}'", command));
+        match command.as_str() {
+            "compare" => {
+                let (day, part) = parse_day_part(args.get(2).ok_or("compare needs a day-part")?)?;
+                let runs = option_value(&args[3..], "--runs")?.unwrap_or(5);
+                Ok(Config::Compare {day, part, runs})
+            },
+            "report" => {
+                let html = option_value(&args[2..], "--html")?.ok_or("report needs --html <file>")?;
+                let runs = option_value(&args[2..], "--runs")?.unwrap_or(5);
+                Ok(Config::Report {html, runs})
+            },
+            "readme" => {
+                let file = option_value(&args[2..], "--file")?.unwrap_or_else(|| readme::README.to_string());
+                let runs = option_value(&args[2..], "--runs")?.unwrap_or(5);
+                Ok(Config::Readme {file, runs})
+            },
+            "bench" => {
+                let day_part = match args.get(2) {
+                    Some(a) if !a.starts_with("--") => Some(parse_day_part(a)?),
+                    _ => None,
+                };
+                let compare = match option_value::<String>(&args[2..], "--against")?.as_deref() {
+                    None | Some("previous") => bench::Baseline::Previous,
+                    Some("best") => bench::Baseline::Best,
+                    Some(other) => return Err(format!("--against must be 'previous' or 'best', not '{}'", other)),
+                };
+                Ok(Config::Bench(bench::BenchOptions {
+                    day_part,
+                    runs: option_value(&args[2..], "--runs")?.unwrap_or(20),
+                    history: option_value(&args[2..], "--history")?
+                        .unwrap_or_else(|| bench::HISTORY_FILE.to_string()),
+                    compare: if has_flag(&args[2..], "--compare") { Some(compare) } else { None },
+                    threshold: option_value(&args[2..], "--threshold")?.unwrap_or(10.0),
+                }))
+            },
+            "solve" => {
+                let (day, part) = parse_day_part(args.get(2).ok_or("solve needs a day-part")?)?;
+                let explain = has_flag(&args[3..], "--explain");
+                let json = has_flag(&args[3..], "--json");
+                Ok(Config::Solve {day, part, explain, json})
+            },
+            "check" => {
+                let day = args.get(2)
+                    .ok_or("check needs a day")?
+                    .parse::<u32>()
+                    .map_err(|_| format!("'{}' is not a day", args[2]))?;
+                let input = option_value(&args[3..], "--input")?;
+                Ok(Config::Check {day, input})
+            },
+            "check-determinism" => {
+                let (day, part) = parse_day_part(
+                    args.get(2).ok_or("check-determinism needs a day-part")?)?;
+                let runs = option_value(&args[3..], "--runs")?.unwrap_or(10);
+                Ok(Config::CheckDeterminism {day, part, runs})
+            },
+            "describe" => {
+                let day = args.get(2)
+                    .ok_or("describe needs a day")?
+                    .parse::<u32>()
+                    .map_err(|_| format!("'{}' is not a day", args[2]))?;
+                let input = option_value(&args[3..], "--input")?;
+                Ok(Config::Describe {day, input, json: has_flag(&args[3..], "--json")})
+            },
+            "dump" => {
+                let day = args.get(2)
+                    .ok_or("dump needs a day")?
+                    .parse::<u32>()
+                    .map_err(|_| format!("'{}' is not a day", args[2]))?;
+                let input = option_value(&args[3..], "--input")?;
+                let output = option_value(&args[3..], "--output")?;
+                Ok(Config::Dump {day, input, output})
+            },
+            "multi" => {
+                let (day, part) = parse_day_part(args.get(2).ok_or("multi needs a day-part")?)?;
+                let inputs = option_values(&args[3..], "--inputs");
+                let runs = option_value(&args[3..], "--runs")?.unwrap_or(1);
+                Ok(Config::Multi {day, part, inputs, runs})
+            },
+            "serve" => {
+                let port = option_value(&args[2..], "--port")?.unwrap_or(8080);
+                Ok(Config::Serve {port})
+            },
+            "reject" => {
+                let (day, part) = parse_day_part(args.get(2).ok_or("reject needs a day-part")?)?;
+                let answer = args.get(3).ok_or("reject needs the answer that was rejected")?;
+                let too_high = has_flag(&args[4..], "--too-high");
+                let too_low = has_flag(&args[4..], "--too-low");
+                let hint = match (too_high, too_low) {
+                    (true, true) => return Err("an answer can't be too high and too low".into()),
+                    (true, false) => rejected::Hint::TooHigh,
+                    (false, true) => rejected::Hint::TooLow,
+                    (false, false) => rejected::Hint::Wrong,
+                };
+                Ok(Config::Reject {day, part, answer: answer.to_string(), hint})
+            },
+            "stream" => {
+                let (day, part) = parse_day_part(args.get(2).ok_or("stream needs a day-part")?)?;
+                let input = option_value(&args[3..], "--input")?;
+                Ok(Config::Stream {day, part, input})
+            },
+            "show" => {
+                let day = args.get(2)
+                    .ok_or("show needs a day")?
+                    .parse::<u32>()
+                    .map_err(|_| format!("'{}' is not a day", args[2]))?;
+                let example = option_value(&args[3..], "--example")?;
+                Ok(Config::Show {day, example})
+            },
+            "batch" => {
+                let manifest = args.get(2).ok_or("batch needs a manifest file")?;
+                let workers = option_value(&args[3..], "--jobs")?.unwrap_or(1);
+                Ok(Config::Batch {manifest: manifest.to_string(), workers})
+            },
+            "header" => Ok(Config::Header),
+            "tui" => Ok(Config::Tui),
+            "all" => Ok(Config::All {use_cache: !has_flag(&args[2..], "--no-cache")}),
+            "identify" => {
+                let file = args.get(2).ok_or("identify needs an input file")?;
+                Ok(Config::Identify {file: file.to_string(), auto: has_flag(&args[3..], "--auto")})
+            },
+            "leaderboard" => {
+                let file = args.get(2).ok_or("leaderboard needs the exported JSON file")?;
+                Ok(Config::Leaderboard {file: file.to_string()})
+            },
+            _ => {
+                let (day, part) = parse_day_part(&command)?;
+                Ok(Config::Run {day, part})
+            },
         }
-        return Ok(Config{day, part});
     }
 }
 
 
+fn parse_day_part(command: &str) -> Result<(u32, u32), String> {
+    let parts = command.split('-').collect::<Vec<&str>>();
+    if parts.len() != 2 {
+        return Err(format!("command '{}' isn't a valid day-part", command));
+    }
+    let day: u32 = parts[0].parse().unwrap_or(0);
+    let part: u32 = parts[1].parse().unwrap_or(0);
+    if !(1..=MAX_DAY).contains(&day) || !(1..=2).contains(&part) {
+        return Err(format!("day or part is not parsable as an int or not in range: input was '{}'", command));
+    }
+    Ok((day, part))
+}
+
+
+/// Find '--name value' in the args and parse the value.
+fn option_value<T: std::str::FromStr>(args: &[String], name: &str) -> Result<Option<T>, String> {
+    match args.iter().position(|a| a == name) {
+        None => Ok(None),
+        Some(i) => args.get(i + 1)
+            .ok_or(format!("{} needs a value", name))?
+            .parse::<T>()
+            .map(Some)
+            .map_err(|_| format!("couldn't parse the value for {}", name)),
+    }
+}
+
+
+/// All the values after '--name' up to the next option, e.g. '--inputs a.txt b.txt --runs 3'
+fn option_values(args: &[String], name: &str) -> Vec<String> {
+    args.iter()
+        .skip_while(|a| *a != name)
+        .skip(1)
+        .take_while(|a| !a.starts_with("--"))
+        .cloned()
+        .collect()
+}
+
+
+fn has_flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|a| a == name)
+}
+
+
 
 fn usage() -> Result<(), String> {
     eprintln!("Usage: aoc2020 <day>-<part>");
+    eprintln!("       aoc2020 compare <day>-<part> [--runs N]");
+    eprintln!("       aoc2020 report --html <file> [--runs N]");
+    eprintln!("       aoc2020 readme [--file README.md] [--runs N]");
+    eprintln!("       aoc2020 bench [<day>-<part>] [--runs N] [--history FILE] [--compare]");
+    eprintln!("                     [--against previous|best] [--threshold PCT]");
+    eprintln!("       aoc2020 batch <jobs.txt> [--jobs N]");
+    eprintln!("       aoc2020 solve <day>-<part> [--explain] [--json]");
+    eprintln!("       aoc2020 check <day> [--input path]");
+    eprintln!("       aoc2020 check-determinism <day>-<part> [--runs N]");
+    eprintln!("       aoc2020 describe <day> [--input path] [--json]");
+    eprintln!("       aoc2020 dump <day> [--input path] [--output file.json]");
+    eprintln!("       aoc2020 multi <day>-<part> --inputs <file>... [--runs N]");
+    eprintln!("       aoc2020 serve [--port 8080]");
+    eprintln!("       aoc2020 reject <day>-<part> <answer> [--too-high|--too-low]");
+    eprintln!("       aoc2020 show <day> [--example name]");
+    eprintln!("       aoc2020 stream <day>-<part> [--input path|-]");
+    eprintln!("       aoc2020 header");
+    eprintln!("       aoc2020 all [--no-cache]");
+    eprintln!("       aoc2020 identify <file> [--auto]");
+    eprintln!("       aoc2020 leaderboard <file.json>");
+    eprintln!("       aoc2020 tui");
     Ok(())
 }
 
 
-fn run_day_part(day: u32, part: u32) {
-    match (day, part) {
-        (1,1) => days::day1_1::day1_1(),
-        (1,2) => days::day1_2::day1_2(),
-        (2,1) => days::day2_1::day2_1(),
-        (2,2) => days::day2_2::day2_2(),
-        (3,1) => days::day3_1::day3_1(),
-        (3,2) => days::day3_2::day3_2(),
-        (4,1) => days::day4_1::day4_1(),
-        (4,2) => days::day4_2::day4_2(),
-        (5,1) => days::day5_1::day5_1(),
-        (5,2) => days::day5_2::day5_2(),
-        (6,1) => days::day6_1::day6_1(),
-        (6,2) => days::day6_2::day6_2(),
-        (7,1) => days::day7_1::day7_1(),
-        (7,2) => days::day7_2::day7_2(),
-        (8,1) => days::day8_1::day8_1(),
-        (8,2) => days::day8_2::day8_2(),
-        (9,1) => days::day9::day9_1(),
-        (9,2) => days::day9::day9_2(),
-        (10,1) => days::day10::day10_1(),
-        (10,2) => days::day10::day10_2(),
-        _ => println!("Day {0}-{1} not defined (yet?)", day, part),
+// run the walkthrough for a day-part, which stops at an input that can't be read or parsed.  The
+// walkthroughs work through the examples first, so only the answers they report for the input are
+// checked against the answers rejected before; returns Ok(false) if any of them was ruled out.
+fn run_day_part(day: u32, part: u32) -> Result<bool, String> {
+    let walkthrough = match registry::walkthrough_for(day, part) {
+        Some(walkthrough) => walkthrough,
+        None => {
+            println!("Day {0}-{1} not defined (yet?)", day, part);
+            return Ok(true);
+        },
+    };
+    let progress = Progress::new(&format!("{}-{}", day, part), io::stderr().is_terminal());
+    let ctx = Context::new(Sink::Stdout, progress);
+    let walked = (walkthrough.run)(&ctx);
+    ctx.progress().finish();
+    walked.map_err(|e| e.to_string())?;
+    let answers = ctx.input_answers();
+    if answers.is_empty() {
+        return Err(format!("Day {}-{} found no answer for the input", day, part));
+    }
+    let mut plausible = true;
+    for (label, answer) in &answers {
+        if matches!(answer, json::Value::Null) {
+            return Err(format!("Day {}-{} found no answer for the input ({})", day, part, label));
+        }
+        // every answer is checked, so that all the warnings are shown
+        plausible &= rejected::warn_if_implausible(day, part, &answer.to_text())?;
+    }
+    Ok(plausible)
+}
+
+
+// print just the answer from the primary solver for a day-part, with the evidence behind it if
+// explain is set.  Returns Ok(false) if the answer was ruled out by an answer rejected before.
+fn solve(day: u32, part: u32, explain: bool, json: bool) -> Result<bool, String> {
+    let solver = *registry::solvers_for(day, part)
+        .first()
+        .ok_or(format!("Day {0}-{1} not defined (yet?)", day, part))?;
+    let input = inputs::read(solver.input)
+        .map_err(|e| format!("couldn't read {}: {}", solver.input, e))?;
+    // no progress line when the output is JSON for another program to read
+    let progress = Progress::new(&format!("{}-{} {}", day, part, solver.name),
+                                 !json && io::stderr().is_terminal());
+    let ctx = Context::new(if json { Sink::Discard } else { Sink::Stdout }, progress);
+    let solution = (solver.solve)(&input, &ctx);
+    ctx.progress().finish();
+    let solution = solution.map_err(|e| e.to_string())?;
+    if json {
+        println!("{}", explain::to_json(solver, &solution, explain));
+    } else {
+        explain::print_readable(&solution, explain);
     }
+    rejected::warn_if_implausible(day, part, &solution.answer)
 }
 
 
@@ -79,6 +330,168 @@ fn main() {
         usage().unwrap();
         process::exit(1);
     });
-    println!("the day is {}-{}", config.day, config.part);
-    run_day_part(config.day, config.part);
+    match config {
+        Config::Run {day, part} => {
+            println!("the day is {}-{}", day, part);
+            match run_day_part(day, part) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::Compare {day, part, runs} => {
+            match compare::compare(day, part, runs) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::Report {html, runs} => {
+            if let Err(e) = report::write_html(&html, runs) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Readme {file, runs} => {
+            if let Err(e) = readme::update_readme(&file, runs) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Bench(options) => {
+            match bench::bench(&options) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::Solve {day, part, explain, json} => {
+            match solve(day, part, explain, json) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::Reject {day, part, answer, hint} => {
+            if let Err(e) = rejected::reject(day, part, &answer, hint) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Multi {day, part, inputs, runs} => {
+            match multi::multi(day, part, &inputs, runs) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::Serve {port} => {
+            if let Err(e) = server::serve(port) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Show {day, example} => {
+            if let Err(e) = show::show(day, example.as_deref()) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Stream {day, part, input} => {
+            if let Err(e) = stream::stream(day, part, input.as_deref()) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Header => print!("{}", ffi::header()),
+        Config::Tui => {
+            if let Err(e) = tui::tui() {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Describe {day, input, json} => {
+            if let Err(e) = describe::describe(day, input.as_deref(), json) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Dump {day, input, output} => {
+            if let Err(e) = dump::dump(day, input.as_deref(), output.as_deref()) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Identify {file, auto} => {
+            match identify::identify(&file, auto) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::Leaderboard {file} => {
+            if let Err(e) = leaderboard::leaderboard(&file) {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        Config::Batch {manifest, workers} => {
+            match batch::batch(&manifest, workers) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::All {use_cache} => {
+            match all::all(use_cache) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::Check {day, input} => {
+            match check::check(day, input.as_deref()) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+        Config::CheckDeterminism {day, part, runs} => {
+            match determinism::check_determinism(day, part, runs) {
+                Ok(true) => {},
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                },
+            }
+        },
+    }
 }
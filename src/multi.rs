@@ -0,0 +1,69 @@
+// Run one day-part across many input files (e.g. the inputs of everyone on the team) and tabulate
+// the answers and timings per file.
+//
+// Inputs that fail to parse are flagged; they are the quickest way to find assumptions that only
+// hold for one person's data.  So are answers that were rejected before (see rejected.rs), though
+// as they may have been rejected for someone else's input, they don't count as failures.
+
+
+use crate::registry::{self, SolveError};
+use crate::rejected::{self, REJECTED_FILE};
+use crate::runner::{self, RunError, RunResult};
+
+
+// a short status for the table
+fn status(result: &RunResult) -> &'static str {
+    match &result.answer {
+        Ok(_) => "ok",
+        Err(RunError::ReadFailed(..)) => "UNREADABLE",
+        Err(RunError::Failed(SolveError::ParseError(_))) => "PARSE ERROR",
+        Err(RunError::Failed(_)) => "NO ANSWER",
+        Err(RunError::Panicked(_)) => "PANIC",
+    }
+}
+
+
+/// Run the primary solver for day-part against each of the inputs; returns Ok(false) if any of
+/// them failed.
+pub fn multi(day: u32, part: u32, inputs: &[String], runs: usize) -> Result<bool, String> {
+    let solver = *registry::solvers_for(day, part)
+        .first()
+        .ok_or(format!("Day {0}-{1} not defined (yet?)", day, part))?;
+    if inputs.is_empty() {
+        return Err("no input files given".to_string());
+    }
+    let rejected = rejected::read_rejected(REJECTED_FILE)?;
+
+    println!("Running {}-{} ({}) on {} input(s)", day, part, solver.name, inputs.len());
+    let results = inputs
+        .iter()
+        .map(|file_name| runner::run_solver_on(solver, file_name, runs, &[]))
+        .collect::<Vec<_>>();
+
+    let width = inputs.iter().map(|i| i.len()).max().unwrap_or(0).max(5);
+    println!("{:<width$}  {:<11} {:>20} {:>12}  input hash", "input", "status", "answer",
+             "median (ms)", width = width);
+    for result in results.iter() {
+        println!("{:<width$}  {:<11} {:>20} {:>12.3}  {}", result.input, status(result),
+                 result.answer.as_ref().map(|a| a.as_str()).unwrap_or("-"),
+                 result.median().as_secs_f64() * 1000.0,
+                 result.input_hash.map(|h| format!("{:016x}", h)).unwrap_or_default(),
+                 width = width);
+        if let Ok(answer) = &result.answer {
+            if let Some(flag) = rejected::flag(day, part, answer, &rejected) {
+                println!("{:<width$}  !!! {}", "", flag, width = width);
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|r| r.answer.is_err()).collect::<Vec<_>>();
+    if !failed.is_empty() {
+        println!("\n{} input(s) failed:", failed.len());
+        for result in failed.iter() {
+            if let Err(e) = &result.answer {
+                println!("  {}: {}", result.input, e);
+            }
+        }
+    }
+    Ok(failed.is_empty())
+}
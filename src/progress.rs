@@ -0,0 +1,106 @@
+// Progress reporting for long-running solvers.
+//
+// A solver that loops over a lot of candidates (e.g. trying each instruction of the day 8 program
+// in turn) calls set_total() once it knows how many there are and inc() as it finishes each one.
+// When enabled, that is drawn as a single line on stderr with the rate and an estimate of the time
+// left, redrawn at most every REDRAW; finish() clears the line again.  A hidden Progress does
+// nothing, which is what solvers get when there is no terminal to draw on or the output is JSON.
+
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+
+const REDRAW: Duration = Duration::from_millis(100);
+
+
+pub struct Progress {
+    label: String,
+    enabled: bool,
+    total: AtomicU64,
+    done: AtomicU64,
+    start: Instant,
+    // when the line was last drawn; None if it hasn't been
+    drawn: Mutex<Option<Instant>>,
+}
+
+
+impl Progress {
+    /// Progress for a solver run, drawn on stderr if enabled.
+    pub fn new(label: &str, enabled: bool) -> Progress {
+        Progress {
+            label: label.to_string(),
+            enabled,
+            total: AtomicU64::new(0),
+            done: AtomicU64::new(0),
+            start: Instant::now(),
+            drawn: Mutex::new(None),
+        }
+    }
+
+    /// Progress that is never shown.
+    pub fn hidden() -> Progress {
+        Progress::new("", false)
+    }
+
+    /// Set the number of steps the solver expects to take.
+    pub fn set_total(&self, total: u64) {
+        self.total.store(total, Ordering::Relaxed);
+        self.draw();
+    }
+
+    /// Count one more step done.
+    pub fn inc(&self) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+        self.draw();
+    }
+
+    /// Clear the progress line, if it was drawn.
+    pub fn finish(&self) {
+        if let Ok(drawn) = self.drawn.lock() {
+            if drawn.is_some() {
+                eprint!("\r\x1b[K");
+            }
+        }
+    }
+
+    fn line(&self) -> String {
+        let total = self.total.load(Ordering::Relaxed);
+        let done = self.done.load(Ordering::Relaxed);
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 { done as f64 / elapsed } else { 0.0 };
+        if total == 0 {
+            return format!("{}: {} done ({:.0}/s)", self.label, done, rate);
+        }
+        let eta = if rate > 0.0 && done < total {
+            format!("{:.1}s", (total - done) as f64 / rate)
+        } else {
+            "-".to_string()
+        };
+        format!("{}: {}/{} ({:.0}%) {:.0}/s, ETA {}", self.label, done, total,
+                100.0 * done as f64 / total as f64, rate, eta)
+    }
+
+    fn draw(&self) {
+        if !self.enabled {
+            return;
+        }
+        let mut drawn = match self.drawn.lock() {
+            Ok(drawn) => drawn,
+            Err(_) => return,
+        };
+        let now = Instant::now();
+        // don't draw at all for solvers that finish before the first redraw would be due
+        let due = match *drawn {
+            Some(last) => now - last >= REDRAW,
+            None => now - self.start >= REDRAW,
+        };
+        if due {
+            eprint!("\r{}\x1b[K", self.line());
+            let _ = io::stderr().flush();
+            *drawn = Some(now);
+        }
+    }
+}
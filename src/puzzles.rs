@@ -0,0 +1,82 @@
+// The puzzle descriptions, kept in `puzzles/day<N>.md` and built into the binary.
+//
+// They are short retellings of each day's puzzle, for reading offline.  The examples from a
+// puzzle are fenced blocks whose info string is `example <name>`, e.g.
+//
+//     ```example passports
+//     ecl:gry pid:860033327 ...
+//     ```
+//
+// and are used as inputs by the demos, the example runs in the TUI and the tests, so that each one
+// is written down just once.
+
+
+const PUZZLES: &[(u32, &str)] = &[
+    (1, include_str!("../puzzles/day1.md")),
+    (2, include_str!("../puzzles/day2.md")),
+    (3, include_str!("../puzzles/day3.md")),
+    (4, include_str!("../puzzles/day4.md")),
+    (5, include_str!("../puzzles/day5.md")),
+    (6, include_str!("../puzzles/day6.md")),
+    (7, include_str!("../puzzles/day7.md")),
+    (8, include_str!("../puzzles/day8.md")),
+    (9, include_str!("../puzzles/day9.md")),
+    (10, include_str!("../puzzles/day10.md")),
+];
+
+
+const FENCE: &str = "```";
+
+
+/// The description of a day's puzzle, as markdown.
+pub fn description(day: u32) -> Option<&'static str> {
+    PUZZLES.iter().find(|(d, _)| *d == day).map(|(_, text)| *text)
+}
+
+
+/// The named examples in a description, in the order they appear.  Each input keeps its final
+/// newline.
+pub fn examples_in(text: &str) -> Vec<(&str, &str)> {
+    let mut examples = Vec::new();
+    let mut open: Option<(&str, usize)> = None;
+    let mut pos = 0;
+    for line in text.split_inclusive('\n') {
+        let start = pos;
+        pos += line.len();
+        let trimmed = line.trim_end();
+        match open {
+            None => {
+                if let Some(info) = trimmed.strip_prefix(FENCE) {
+                    let mut words = info.split_whitespace();
+                    // other fenced blocks are ignored, but still have to be skipped over
+                    let name = match (words.next(), words.next()) {
+                        (Some("example"), Some(name)) => name,
+                        _ => "",
+                    };
+                    open = Some((name, pos));
+                }
+            },
+            Some((name, body)) => {
+                if trimmed == FENCE {
+                    if !name.is_empty() {
+                        examples.push((name, &text[body..start]));
+                    }
+                    open = None;
+                }
+            },
+        }
+    }
+    examples
+}
+
+
+/// The named examples from a day's puzzle.
+pub fn examples(day: u32) -> Vec<(&'static str, &'static str)> {
+    description(day).map(examples_in).unwrap_or_default()
+}
+
+
+/// An example from a day's puzzle by name.
+pub fn example(day: u32, name: &str) -> Option<&'static str> {
+    examples(day).into_iter().find(|(n, _)| *n == name).map(|(_, input)| input)
+}
@@ -0,0 +1,73 @@
+// Regenerate the results table in README.md from an actual run of the solvers.
+//
+// Only the text between the START and END markers is replaced, so the rest of the README is left
+// exactly as it was written.
+
+
+use std::fmt::Write;
+
+use crate::registry;
+use crate::runner::{self, RunResult, Verification};
+
+
+pub const README: &str = "README.md";
+
+const START: &str = "<!-- results:start -->";
+const END: &str = "<!-- results:end -->";
+
+
+/// Run all the solvers and replace the results section of the README in file_name.
+pub fn update_readme(file_name: &str, runs: usize) -> Result<(), String> {
+    let readme = std::fs::read_to_string(file_name)
+        .map_err(|e| format!("couldn't read {}: {}", file_name, e))?;
+    let start = readme.find(START)
+        .ok_or(format!("{} has no '{}' marker", file_name, START))?;
+    let end = readme.find(END)
+        .ok_or(format!("{} has no '{}' marker", file_name, END))?;
+    if end < start {
+        return Err(format!("'{}' comes before '{}' in {}", END, START, file_name));
+    }
+
+    let results = runner::run_all(runs)?;
+    let updated = format!("{}\n{}{}", &readme[..start + START.len()], render_table(&results),
+                          &readme[end..]);
+    std::fs::write(file_name, updated).map_err(|e| format!("couldn't write {}: {}", file_name, e))?;
+    println!("Updated the results table in {}", file_name);
+    Ok(())
+}
+
+
+fn render_table(results: &[RunResult]) -> String {
+    let mut table = String::new();
+    writeln!(table, "| Day | Title | Stars | Median runtime | Source |").unwrap();
+    writeln!(table, "|----:|-------|-------|---------------:|--------|").unwrap();
+    for day in registry::DAYS.iter() {
+        let parts = results.iter().filter(|r| r.day == day.day).collect::<Vec<_>>();
+        if parts.is_empty() {
+            continue;
+        }
+        let stars = parts.iter()
+            .filter(|r| r.answer.is_ok() && r.verification == Verification::Correct)
+            .map(|_| "⭐")
+            .collect::<String>();
+        let runtimes = parts.iter()
+            .map(|r| match r.answer {
+                Ok(_) => format!("{:.3} ms", r.median().as_secs_f64() * 1000.0),
+                Err(_) => "error".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" / ");
+        let mut sources: Vec<&str> = Vec::new();
+        for source in registry::SOLVERS.iter().filter(|s| s.day == day.day).map(|s| s.source) {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        let links = sources.iter()
+            .map(|s| format!("[{}]({})", s.rsplit('/').next().unwrap_or(s), s))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(table, "| {} | {} | {} | {} | {} |", day.day, day.title, stars, runtimes, links).unwrap();
+    }
+    table
+}
@@ -0,0 +1,333 @@
+// The registry of solvers.
+//
+// Every day-part has at least one solver registered here; the first one registered for a day-part
+// is the primary solver.  Further solvers are alternative implementations (e.g. a naive and a
+// clever version) that can be run side-by-side with `aoc2020 compare <day>-<part>`.
+
+
+use std::fmt;
+use std::io::BufRead;
+
+use thiserror::Error;
+
+use crate::days;
+use crate::context::Context;
+use crate::json::Value;
+use crate::puzzles;
+use crate::utils::InputError;
+
+
+#[derive(Error, Debug, Clone)]
+pub enum SolveError {
+    #[error("couldn't parse input: {0}")]
+    ParseError(String),
+    #[error("no answer found: {0}")]
+    NoAnswer(String),
+}
+
+
+/// What a solver found: the answer and, for --explain, the evidence behind it (e.g. the entries
+/// that add up to 2020).  Evidence is kept in the order the solver added it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub answer: String,
+    pub evidence: Vec<(String, Value)>,
+}
+
+
+impl Solution {
+    pub fn new<T: fmt::Display>(answer: T) -> Self {
+        Solution { answer: answer.to_string(), evidence: Vec::new() }
+    }
+
+    /// Add a piece of evidence to the solution.
+    pub fn with<V: Into<Value>>(mut self, label: &str, value: V) -> Self {
+        self.evidence.push((label.to_string(), value.into()));
+        self
+    }
+}
+
+
+/// A solver takes the whole of the puzzle input and returns the solution.  Anything else it has to
+/// say, and how far it has got if it takes a while, goes through the Context.
+pub type SolveFn = fn(&str, &Context) -> Result<Solution, SolveError>;
+
+
+/// A streaming solver reads its input a line at a time as it goes, rather than all at once, so it
+/// can take inputs (e.g. generated ones) far bigger than memory.  The first argument names the
+/// input for errors.
+pub type StreamFn = fn(&str, &mut dyn BufRead, &Context) -> Result<Solution, SolveError>;
+
+
+/// The year the puzzles are from.
+pub const YEAR: u32 = 2020;
+
+
+pub struct Solver {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    /// Bump this when a change to the solver could change its answer; cached answers from other
+    /// versions are then ignored.
+    pub version: &'static str,
+    pub input: &'static str,
+    pub source: &'static str,
+    pub solve: SolveFn,
+}
+
+
+// solver!(day, part, name, version, input, module::function) registers days::module::function; the
+// source file is worked out from the module name.
+macro_rules! solver {
+    ($day:expr, $part:expr, $name:expr, $version:expr, $input:expr, $module:ident :: $func:ident) => {
+        Solver {
+            day: $day,
+            part: $part,
+            name: $name,
+            version: $version,
+            input: $input,
+            source: concat!("src/days/", stringify!($module), ".rs"),
+            solve: days::$module::$func,
+        }
+    };
+}
+
+
+/// A line of input that a day's parser rejected; `line` is 1-based.
+#[derive(Debug, Clone)]
+pub struct BadLine {
+    pub line: usize,
+    pub text: String,
+    pub error: String,
+}
+
+
+/// What a day's parser made of an input: how many lines it checked (or batches of lines, for the
+/// days whose records span several lines) and the ones it rejected.
+#[derive(Debug, Clone)]
+pub struct Checked {
+    pub checked: usize,
+    pub bad: Vec<BadLine>,
+}
+
+
+/// Run just a day's parser over an input and return every line that it rejects.
+pub type CheckFn = fn(&str) -> Checked;
+
+/// Parse an input into the day's domain objects, as JSON.
+pub type DumpFn = fn(&str) -> Result<Value, SolveError>;
+
+/// Statistics about an input that are particular to the day (e.g. how deep the bags nest), as JSON.
+pub type DescribeFn = fn(&str) -> Result<Value, SolveError>;
+
+
+pub struct Day {
+    pub day: u32,
+    pub title: &'static str,
+    pub check: CheckFn,
+    pub dump: DumpFn,
+    pub describe: DescribeFn,
+}
+
+
+// the parser of a day lives in the module of its first part.
+macro_rules! day {
+    ($day:expr, $title:expr, $module:ident) => {
+        Day {
+            day: $day,
+            title: $title,
+            check: days::$module::check,
+            dump: days::$module::dump,
+            describe: days::$module::describe,
+        }
+    };
+}
+
+
+pub const DAYS: &[Day] = &[
+    day!(1, "Report Repair", day1_1),
+    day!(2, "Password Philosophy", day2_1),
+    day!(3, "Toboggan Trajectory", day3_1),
+    day!(4, "Passport Processing", day4_1),
+    day!(5, "Binary Boarding", day5_1),
+    day!(6, "Custom Customs", day6_1),
+    day!(7, "Handy Haversacks", day7_1),
+    day!(8, "Handheld Halting", day8_1),
+    day!(9, "Encoding Error", day9),
+    day!(10, "Adapter Array", day10),
+];
+
+
+pub const SOLVERS: &[Solver] = &[
+    solver!(1, 1, "pairs", "1", "./input/day1-1.txt", day1_1::solve),
+    solver!(1, 2, "naive", "1", "./input/day1-1.txt", day1_2::solve),
+    solver!(1, 2, "two-pointer", "1", "./input/day1-1.txt", day1_2::solve_two_pointer),
+    solver!(2, 1, "count", "1", "./input/day2-1.txt", day2_1::solve),
+    solver!(2, 2, "positions", "1", "./input/day2-1.txt", day2_2::solve),
+    solver!(3, 1, "slope", "1", "./input/day3-1.txt", day3_1::solve),
+    solver!(3, 2, "slopes", "1", "./input/day3-1.txt", day3_2::solve),
+    solver!(4, 1, "fields", "2", "./input/day4-1.txt", day4_1::solve),
+    solver!(4, 2, "validate", "2", "./input/day4-1.txt", day4_2::solve),
+    solver!(5, 1, "highest", "1", "./input/day5-1.txt", day5_1::solve),
+    solver!(5, 2, "missing", "1", "./input/day5-1.txt", day5_2::solve),
+    solver!(6, 1, "anyone", "1", "./input/day6.txt", day6_1::solve),
+    solver!(6, 2, "everyone", "1", "./input/day6.txt", day6_2::solve),
+    solver!(7, 1, "held-by", "1", "./input/day7.txt", day7_1::solve),
+    solver!(7, 2, "contains", "1", "./input/day7.txt", day7_2::solve),
+    solver!(8, 1, "run", "1", "./input/day8.txt", day8_1::solve),
+    solver!(8, 2, "brute-force", "1", "./input/day8.txt", day8_2::solve),
+    solver!(8, 2, "graph", "1", "./input/day8.txt", day8_2::solve_graph),
+    solver!(9, 1, "window", "1", "./input/day9.txt", day9::solve_1),
+    solver!(9, 2, "sequence", "1", "./input/day9.txt", day9::solve_2),
+    solver!(10, 1, "intervals", "1", "./input/day10.txt", day10::solve_1),
+    solver!(10, 2, "paths", "1", "./input/day10.txt", day10::solve_2),
+];
+
+
+/// A streaming solver for a day-part, for the day-parts that can be solved a line at a time.
+pub struct Streamer {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    pub solve: StreamFn,
+}
+
+
+pub const STREAMERS: &[Streamer] = &[
+    Streamer { day: 2, part: 1, name: "count", solve: days::day2_1::solve_streaming },
+    Streamer { day: 2, part: 2, name: "positions", solve: days::day2_2::solve_streaming },
+    Streamer { day: 5, part: 1, name: "highest", solve: days::day5_1::solve_streaming },
+    Streamer { day: 5, part: 2, name: "missing", solve: days::day5_2::solve_streaming },
+    Streamer { day: 9, part: 1, name: "window", solve: days::day9::solve_1_streaming },
+];
+
+
+/// A day-part's walkthrough, as run by `aoc2020 <day>-<part>`: it works through the examples and
+/// the input, saying what it finds on the way and reporting answers through the Context.  It
+/// stops at an input that can't be read or parsed.
+pub type WalkthroughFn = fn(&Context) -> Result<(), InputError>;
+
+
+pub struct Walkthrough {
+    pub day: u32,
+    pub part: u32,
+    pub run: WalkthroughFn,
+}
+
+
+pub const WALKTHROUGHS: &[Walkthrough] = &[
+    Walkthrough { day: 1, part: 1, run: days::day1_1::day1_1 },
+    Walkthrough { day: 1, part: 2, run: days::day1_2::day1_2 },
+    Walkthrough { day: 2, part: 1, run: days::day2_1::day2_1 },
+    Walkthrough { day: 2, part: 2, run: days::day2_2::day2_2 },
+    Walkthrough { day: 3, part: 1, run: days::day3_1::day3_1 },
+    Walkthrough { day: 3, part: 2, run: days::day3_2::day3_2 },
+    Walkthrough { day: 4, part: 1, run: days::day4_1::day4_1 },
+    Walkthrough { day: 4, part: 2, run: days::day4_2::day4_2 },
+    Walkthrough { day: 5, part: 1, run: days::day5_1::day5_1 },
+    Walkthrough { day: 5, part: 2, run: days::day5_2::day5_2 },
+    Walkthrough { day: 6, part: 1, run: days::day6_1::day6_1 },
+    Walkthrough { day: 6, part: 2, run: days::day6_2::day6_2 },
+    Walkthrough { day: 7, part: 1, run: days::day7_1::day7_1 },
+    Walkthrough { day: 7, part: 2, run: days::day7_2::day7_2 },
+    Walkthrough { day: 8, part: 1, run: days::day8_1::day8_1 },
+    Walkthrough { day: 8, part: 2, run: days::day8_2::day8_2 },
+    Walkthrough { day: 9, part: 1, run: days::day9::day9_1 },
+    Walkthrough { day: 9, part: 2, run: days::day9::day9_2 },
+    Walkthrough { day: 10, part: 1, run: days::day10::day10_1 },
+    Walkthrough { day: 10, part: 2, run: days::day10::day10_2 },
+];
+
+
+/// An example from a puzzle's description (by its name in `puzzles/day<N>.md`) and the answer the
+/// puzzle gives for it.
+pub struct Example {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    pub answer: &'static str,
+}
+
+
+impl Example {
+    /// The example's input; None if the puzzle description has no example of that name.
+    pub fn input(&self) -> Option<&'static str> {
+        puzzles::example(self.day, self.name)
+    }
+}
+
+
+// Day 9's example isn't here: it uses a preamble of 5 numbers, and the solvers use 25.
+pub const EXAMPLES: &[Example] = &[
+    Example { day: 1, part: 1, name: "report", answer: "514579" },
+    Example { day: 1, part: 2, name: "report", answer: "241861950" },
+    Example { day: 2, part: 1, name: "passwords", answer: "2" },
+    Example { day: 2, part: 2, name: "passwords", answer: "1" },
+    Example { day: 3, part: 1, name: "map", answer: "7" },
+    Example { day: 3, part: 2, name: "map", answer: "336" },
+    Example { day: 4, part: 1, name: "passports", answer: "2" },
+    Example { day: 4, part: 2, name: "checked-passports", answer: "4" },
+    Example { day: 5, part: 1, name: "boarding-passes", answer: "820" },
+    Example { day: 6, part: 1, name: "answers", answer: "11" },
+    Example { day: 6, part: 2, name: "answers", answer: "6" },
+    Example { day: 7, part: 1, name: "rules", answer: "4" },
+    Example { day: 7, part: 2, name: "rules", answer: "32" },
+    Example { day: 7, part: 2, name: "deep-rules", answer: "126" },
+    Example { day: 8, part: 1, name: "program", answer: "5" },
+    Example { day: 8, part: 2, name: "program", answer: "8" },
+    Example { day: 10, part: 1, name: "adapters", answer: "220" },
+    Example { day: 10, part: 1, name: "small-adapters", answer: "35" },
+    Example { day: 10, part: 2, name: "adapters", answer: "19208" },
+    Example { day: 10, part: 2, name: "small-adapters", answer: "8" },
+];
+
+
+/// Return all the solvers registered for a day-part, primary solver first.
+pub fn solvers_for(day: u32, part: u32) -> Vec<&'static Solver> {
+    SOLVERS
+        .iter()
+        .filter(|s| s.day == day && s.part == part)
+        .collect()
+}
+
+
+/// The primary solver for each registered day-part, in the order they were registered.
+pub fn primary_solvers() -> Vec<&'static Solver> {
+    let mut primaries: Vec<&'static Solver> = Vec::new();
+    for solver in SOLVERS.iter() {
+        if !primaries.iter().any(|p| p.day == solver.day && p.part == solver.part) {
+            primaries.push(solver);
+        }
+    }
+    primaries
+}
+
+
+/// The entry for a day, if there is one.
+pub fn day(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+
+/// The input a day's parser is checked against by default: that of its primary part 1 solver.
+pub fn default_input(day: u32) -> Option<&'static str> {
+    solvers_for(day, 1).first().map(|s| s.input)
+}
+
+
+/// The streaming solver for a day-part, if it has one.
+pub fn streamer_for(day: u32, part: u32) -> Option<&'static Streamer> {
+    STREAMERS.iter().find(|s| s.day == day && s.part == part)
+}
+
+
+/// The walkthrough for a day-part, if it has one.
+pub fn walkthrough_for(day: u32, part: u32) -> Option<&'static Walkthrough> {
+    WALKTHROUGHS.iter().find(|w| w.day == day && w.part == part)
+}
+
+
+/// The (first) example for a day-part, if it has one.
+pub fn example_for(day: u32, part: u32) -> Option<&'static Example> {
+    EXAMPLES.iter().find(|e| e.day == day && e.part == part)
+}
@@ -0,0 +1,185 @@
+// A local record of answers that were rejected, so that the same mistake isn't made twice.
+//
+// Each line of the record is an answer that was rejected for a day-part, with the hint that came
+// with it if there was one:
+//
+//     <day>-<part> <answer> [too-high|too-low]
+//
+// e.g. '9-2 35602097 too-low'.  A new answer for a day-part is implausible if it was rejected
+// before, or if it is higher than an answer that was too high or lower than one that was too low.
+// An implausible answer gets a warning and is not to be submitted.  The record is kept next to
+// the answer cache rather than in input/, as it is local to whoever is solving.
+
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+
+pub const REJECTED_FILE: &str = "rejected-answers.txt";
+
+
+#[derive(Error, Debug, Clone)]
+pub enum RejectedError {
+    #[error("corrupted rejected answer line: {0}")]
+    DecodeError(String),
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::TooHigh => write!(f, "too-high"),
+            Hint::TooLow => write!(f, "too-low"),
+            Hint::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+
+/// An answer that was rejected for a day-part.
+#[derive(Debug, Clone)]
+pub struct Rejected {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub hint: Hint,
+}
+
+
+impl FromStr for Rejected {
+    type Err = RejectedError;
+
+    // parses '9-2 35602097 too-low'; without a hint the answer was just wrong
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let corrupt = || RejectedError::DecodeError(line.to_string());
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        let (day_part, answer, hint) = match parts.as_slice() {
+            [day_part, answer] => (day_part, answer, Hint::Wrong),
+            [day_part, answer, "too-high"] => (day_part, answer, Hint::TooHigh),
+            [day_part, answer, "too-low"] => (day_part, answer, Hint::TooLow),
+            _ => return Err(corrupt()),
+        };
+        let (day, part) = day_part.split_once('-').ok_or_else(corrupt)?;
+        let day = day.parse::<u32>().map_err(|_| corrupt())?;
+        let part = part.parse::<u32>().map_err(|_| corrupt())?;
+        Ok(Rejected {day, part, answer: answer.to_string(), hint})
+    }
+}
+
+
+impl fmt::Display for Rejected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{} {}", self.day, self.part, self.answer)?;
+        match self.hint {
+            Hint::Wrong => Ok(()),
+            hint => write!(f, " {}", hint),
+        }
+    }
+}
+
+
+/// Read the record of rejected answers; a missing file just means nothing has been rejected, but
+/// one that can't be read is an error.  Blank lines and lines starting with '#' are ignored.
+pub fn read_rejected(file_name: &str) -> Result<Vec<Rejected>, String> {
+    let data = match fs::read_to_string(file_name) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("couldn't read {}: {}", file_name, e)),
+    };
+    data.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|(n, l)| {
+            l.parse::<Rejected>().map_err(|e| format!("{}:{}: {}", file_name, n + 1, e))
+        })
+        .collect()
+}
+
+
+/// Add a rejected answer to the record, creating it if need be.
+pub fn record(file_name: &str, rejected: &Rejected) -> Result<(), String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_name)
+        .and_then(|mut file| writeln!(file, "{}", rejected))
+        .map_err(|e| format!("couldn't write {}: {}", file_name, e))
+}
+
+
+/// Every reason that an answer for a day-part can't be right, going by the answers that were
+/// rejected for it; empty if it might be.  Answers that aren't numbers are only compared exactly.
+pub fn implausible(day: u32, part: u32, answer: &str, rejected: &[Rejected]) -> Vec<String> {
+    let number = answer.parse::<i128>().ok();
+    rejected
+        .iter()
+        .filter(|r| r.day == day && r.part == part)
+        .filter_map(|r| {
+            let bound = r.answer.parse::<i128>().ok();
+            match (r.hint, number, bound) {
+                _ if r.answer == answer => {
+                    Some(format!("{} was already rejected ({})", answer, r.hint))
+                },
+                (Hint::TooHigh, Some(n), Some(b)) if n > b => {
+                    Some(format!("{} is higher than {}, which was too high", answer, r.answer))
+                },
+                (Hint::TooLow, Some(n), Some(b)) if n < b => {
+                    Some(format!("{} is lower than {}, which was too low", answer, r.answer))
+                },
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+
+/// A warning to show beside an answer in a table, if the answer is implausible.
+pub fn flag(day: u32, part: u32, answer: &str, rejected: &[Rejected]) -> Option<String> {
+    let reasons = implausible(day, part, answer, rejected);
+    match reasons.is_empty() {
+        true => None,
+        false => Some(format!("DO NOT SUBMIT: {}", reasons.join("; "))),
+    }
+}
+
+
+/// Record an answer as rejected for a day-part.
+pub fn reject(day: u32, part: u32, answer: &str, hint: Hint) -> Result<(), String> {
+    if answer.split_whitespace().count() != 1 {
+        return Err(format!("'{}' isn't an answer", answer));
+    }
+    let rejected = Rejected {day, part, answer: answer.to_string(), hint};
+    record(REJECTED_FILE, &rejected)?;
+    println!("Recorded {} in {}", rejected, REJECTED_FILE);
+    Ok(())
+}
+
+
+/// Print a warning that can't be missed for an implausible answer.  Returns false if there was
+/// anything to warn about, i.e. the answer is not to be submitted.
+pub fn warn_if_implausible(day: u32, part: u32, answer: &str) -> Result<bool, String> {
+    let reasons = implausible(day, part, answer, &read_rejected(REJECTED_FILE)?);
+    if reasons.is_empty() {
+        return Ok(true);
+    }
+    let rule = "!".repeat(72);
+    eprintln!("{}", rule);
+    eprintln!("!!! {}-{}: DO NOT SUBMIT {}", day, part, answer);
+    for reason in &reasons {
+        eprintln!("!!!   {}", reason);
+    }
+    eprintln!("{}", rule);
+    Ok(false)
+}
@@ -0,0 +1,125 @@
+// Write a single, self-contained HTML page describing a full run of the solvers.
+//
+// The page has a summary, a table of every day-part (answer, verification status, timings, input
+// hash and any error or panic) and a bar chart of the median timings drawn as inline SVG, so it
+// can be shared without any other files.
+
+
+use std::fmt::Write;
+
+use crate::runner::{self, RunResult, Verification};
+use crate::utils;
+
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }
+th { background: #f0f0f0; }
+td.num { text-align: right; font-family: monospace; }
+td.mono { font-family: monospace; }
+.correct { color: #080; }
+.wrong { color: #c00; font-weight: bold; }
+.unverified { color: #888; }
+.error { color: #c60; font-weight: bold; }
+";
+
+
+/// Run all the solvers and write the report to file_name.
+pub fn write_html(file_name: &str, runs: usize) -> Result<(), String> {
+    let results = runner::run_all(runs)?;
+    let html = render_html(&results, utils::now_timestamp());
+    std::fs::write(file_name, html).map_err(|e| format!("couldn't write {}: {}", file_name, e))?;
+    println!("Wrote report of {} day-parts to {}", results.len(), file_name);
+    Ok(())
+}
+
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+
+// the css class and the text for the status column.
+fn status(result: &RunResult) -> (&'static str, String) {
+    match (&result.answer, &result.verification) {
+        (Err(_), _) => ("error", "error".to_string()),
+        (Ok(_), Verification::Correct) => ("correct", "&#10004; correct".to_string()),
+        (Ok(_), v @ Verification::Wrong(_)) => ("wrong", format!("&#10008; {}", escape(&v.to_string()))),
+        (Ok(_), Verification::Unverified) => ("unverified", "? unverified".to_string()),
+    }
+}
+
+
+fn millis(result: &RunResult) -> f64 {
+    result.median().as_secs_f64() * 1000.0
+}
+
+
+fn render_html(results: &[RunResult], generated: u64) -> String {
+    let mut html = String::new();
+    let count = |class: &str| results.iter().filter(|r| status(r).0 == class).count();
+
+    writeln!(html, "<!DOCTYPE html>").unwrap();
+    writeln!(html, "<html><head><meta charset=\"utf-8\"><title>Advent of Code 2020 report</title>").unwrap();
+    writeln!(html, "<style>{}</style></head><body>", STYLE).unwrap();
+    writeln!(html, "<h1>Advent of Code 2020 report</h1>").unwrap();
+    writeln!(html, "<p>Generated {}. {} day-parts: {} correct, {} wrong, {} unverified, {} errors.</p>",
+             utils::format_timestamp(generated), results.len(), count("correct"), count("wrong"),
+             count("unverified"), count("error")).unwrap();
+
+    writeln!(html, "<table>").unwrap();
+    writeln!(html, "<tr><th>Day</th><th>Solver</th><th>Answer</th><th>Status</th>\
+                    <th>Median (ms)</th><th>Runs</th><th>Input</th><th>Input hash</th>\
+                    <th>Error</th></tr>").unwrap();
+    for result in results {
+        let (class, text) = status(result);
+        let answer = result.answer.as_ref().map(|a| escape(a)).unwrap_or_default();
+        let error = result.answer.as_ref().err().map(|e| escape(&e.to_string())).unwrap_or_default();
+        let hash = result.input_hash.map(|h| format!("{:016x}", h)).unwrap_or_default();
+        writeln!(html, "<tr><td>{}-{}</td><td>{}</td><td class=\"mono\">{}</td>\
+                        <td class=\"{}\">{}</td><td class=\"num\">{:.3}</td><td class=\"num\">{}</td>\
+                        <td class=\"mono\">{}</td><td class=\"mono\">{}</td><td class=\"error\">{}</td></tr>",
+                 result.day, result.part, escape(result.name), answer, class, text, millis(result),
+                 result.timings.len(), escape(&result.input), hash, error).unwrap();
+    }
+    writeln!(html, "</table>").unwrap();
+
+    writeln!(html, "<h2>Median timings</h2>").unwrap();
+    html.push_str(&render_chart(results));
+    writeln!(html, "</body></html>").unwrap();
+    html
+}
+
+
+// a horizontal bar chart of the median timing of each day-part, scaled to the slowest one.
+fn render_chart(results: &[RunResult]) -> String {
+    const LABEL: f64 = 60.0;
+    const WIDTH: f64 = 500.0;
+    const BAR: f64 = 20.0;
+    let slowest = results.iter().map(millis).fold(0.0, f64::max).max(f64::MIN_POSITIVE);
+    let height = BAR * results.len() as f64 + 10.0;
+
+    let mut svg = String::new();
+    writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+                   font-family=\"sans-serif\" font-size=\"12\">", LABEL + WIDTH + 100.0, height).unwrap();
+    for (i, result) in results.iter().enumerate() {
+        let y = i as f64 * BAR + 5.0;
+        let width = (millis(result) / slowest * WIDTH).max(1.0);
+        let colour = match status(result).0 {
+            "correct" => "#4a4",
+            "wrong" | "error" => "#c44",
+            _ => "#999",
+        };
+        writeln!(svg, "<text x=\"0\" y=\"{}\">{}-{}</text>", y + 14.0, result.day, result.part).unwrap();
+        writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"/>",
+                 LABEL, y + 2.0, width, BAR - 4.0, colour).unwrap();
+        writeln!(svg, "<text x=\"{:.1}\" y=\"{}\">{:.3} ms</text>",
+                 LABEL + width + 5.0, y + 14.0, millis(result)).unwrap();
+    }
+    writeln!(svg, "</svg>").unwrap();
+    svg
+}
@@ -0,0 +1,168 @@
+// Run solvers and collect their results.
+//
+// A solver is run against its input a number of times; the answer, the timings, a hash of the
+// input and whether the answer matches the verified answer in ./input/answers.txt are collected
+// into a RunResult.  Panics in a solver are caught and reported as an error for that day-part.
+
+
+use std::fmt;
+use std::io::{self, IsTerminal};
+use std::panic;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::answers::{self, Expected};
+use crate::context::{Context, Sink};
+use crate::inputs;
+use crate::progress::Progress;
+use crate::registry::{self, SolveError, Solver};
+use crate::utils;
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verification {
+    Correct,
+    Wrong(String),
+    Unverified,
+}
+
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verification::Correct => write!(f, "correct"),
+            Verification::Wrong(expected) => write!(f, "wrong (expected {})", expected),
+            Verification::Unverified => write!(f, "unverified"),
+        }
+    }
+}
+
+
+/// Why a run didn't produce an answer.
+#[derive(Error, Debug, Clone)]
+pub enum RunError {
+    #[error("couldn't read {0}: {1}")]
+    ReadFailed(String, String),
+    #[error("{0}")]
+    Failed(SolveError),
+    #[error("panicked: {0}")]
+    Panicked(String),
+}
+
+
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub day: u32,
+    pub part: u32,
+    pub name: &'static str,
+    pub input: String,
+    pub input_hash: Option<u64>,
+    pub answer: Result<String, RunError>,
+    pub timings: Vec<Duration>,
+    pub verification: Verification,
+}
+
+
+impl RunResult {
+    pub fn median(&self) -> Duration {
+        utils::median(&self.timings)
+    }
+}
+
+
+/// Run a solver `runs` times against its own input.
+pub fn run_solver(solver: &'static Solver, runs: usize, expected: &[Expected]) -> RunResult {
+    run_solver_on(solver, solver.input, runs, expected)
+}
+
+
+/// Run a solver `runs` times against the input in file_name.  The solver is not run again once it
+/// has errored or panicked.
+pub fn run_solver_on(solver: &'static Solver, file_name: &str, runs: usize, expected: &[Expected])
+    -> RunResult
+{
+    // keep the default panic output quiet while the solver runs; we report it ourselves.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = run_solver_with(solver, file_name, runs, expected, io::stderr().is_terminal());
+    panic::set_hook(hook);
+    result
+}
+
+
+/// Run a solver like run_solver_on but without drawing its progress, for when several solvers are
+/// running at once.  The panic hook is left alone, as swapping it from several threads would race;
+/// the caller should quiet it once for all of them.
+pub fn run_solver_quietly(solver: &'static Solver, file_name: &str, runs: usize,
+                          expected: &[Expected]) -> RunResult
+{
+    run_solver_with(solver, file_name, runs, expected, false)
+}
+
+
+fn run_solver_with(solver: &'static Solver, file_name: &str, runs: usize, expected: &[Expected],
+                   show_progress: bool) -> RunResult
+{
+    let mut result = RunResult {
+        day: solver.day,
+        part: solver.part,
+        name: solver.name,
+        input: file_name.to_string(),
+        input_hash: None,
+        answer: Err(RunError::ReadFailed(file_name.to_string(), "not read".to_string())),
+        timings: Vec::new(),
+        verification: Verification::Unverified,
+    };
+    let input = match inputs::read(file_name) {
+        Ok(input) => input,
+        Err(e) => {
+            result.answer = Err(RunError::ReadFailed(file_name.to_string(), e.to_string()));
+            return result;
+        },
+    };
+    result.input_hash = Some(utils::hash_input(&input));
+
+    let label = format!("{}-{} {}", solver.day, solver.part, solver.name);
+    for _ in 0..runs.max(1) {
+        let ctx = Context::new(Sink::Discard, Progress::new(&label, show_progress));
+        let start = Instant::now();
+        let outcome = panic::catch_unwind(|| (solver.solve)(&input, &ctx));
+        result.timings.push(start.elapsed());
+        ctx.progress().finish();
+        result.answer = match outcome {
+            Ok(Ok(solution)) => Ok(solution.answer),
+            Ok(Err(e)) => Err(RunError::Failed(e)),
+            Err(payload) => Err(RunError::Panicked(utils::panic_message(payload))),
+        };
+        if result.answer.is_err() {
+            break;
+        }
+    }
+
+    if let Ok(answer) = &result.answer {
+        result.verification = verify(solver.day, solver.part, answer, expected);
+    }
+    result
+}
+
+
+/// Check an answer against the verified answer for its day-part, if there is one.
+pub fn verify(day: u32, part: u32, answer: &str, expected: &[Expected]) -> Verification {
+    match answers::expected_for(expected, day, part) {
+        Some(e) if e.answer == answer => Verification::Correct,
+        Some(e) => Verification::Wrong(e.answer.clone()),
+        None => Verification::Unverified,
+    }
+}
+
+
+/// Run the primary solver for every registered day-part.
+pub fn run_all(runs: usize) -> Result<Vec<RunResult>, String> {
+    let expected = answers::read_expected(answers::ANSWERS_FILE)?;
+    let mut results = Vec::new();
+    for solver in registry::primary_solvers() {
+        results.push(run_solver(solver, runs, &expected));
+    }
+    Ok(results)
+}
@@ -0,0 +1,250 @@
+// A small HTTP/1.1 server so that other tools can call the solvers over localhost.
+//
+//     GET  /days                  -> the registered solvers
+//     POST /solve/{day}/{part}    -> the answer for the puzzle input in the body
+//
+// POST /solve takes an optional '?solver=<name>' query to pick one of the alternative solvers.
+// Everything is returned as JSON; errors are {"error": "..."} with a suitable status code.  Each
+// connection is handled on its own thread and closed after one request.  A client that is too slow
+// to send its request gets a 408, and one whose request line and headers are too long a 431.
+
+
+use std::cell::Cell;
+use std::io::prelude::*;
+use std::io::{self, BufReader};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::panic;
+use std::thread;
+use std::time::Duration;
+
+use crate::explain;
+use crate::json::{self, Value};
+use crate::context::Context;
+use crate::registry::{self, SolveError};
+use crate::utils;
+
+
+const MAX_BODY: usize = 64 * 1024 * 1024;
+const MAX_HEADER: u64 = 16 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+
+thread_local! {
+    // set while a solver runs, so that its panic is reported as a 500 rather than on stderr
+    static SOLVING: Cell<bool> = const { Cell::new(false) };
+}
+
+
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+
+type Response = (u16, Value);
+
+
+fn error(status: u16, message: &str) -> Response {
+    (status, json::object(vec![("error", message.into())]))
+}
+
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+
+// a read that failed because the client stopped sending is a timeout; anything else is just bad.
+fn read_error(e: io::Error) -> Response {
+    match e.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            error(408, "timed out waiting for the request")
+        },
+        _ => error(400, &e.to_string()),
+    }
+}
+
+
+// read one line of the request line and headers, which between them can't be more than
+// MAX_HEADER bytes.
+fn read_header_line(reader: &mut io::Take<BufReader<&TcpStream>>) -> Result<String, Response> {
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(read_error)?;
+    if !line.ends_with('\n') {
+        return Err(match reader.limit() {
+            0 => error(431, "the request line and headers are too large"),
+            _ => error(400, "the request ended in its headers"),
+        });
+    }
+    Ok(line)
+}
+
+
+fn read_request(stream: &TcpStream) -> Result<Request, Response> {
+    let mut reader = BufReader::new(stream).take(MAX_HEADER);
+    let line = read_header_line(&mut reader)?;
+    let bits = line.split_whitespace().collect::<Vec<_>>();
+    if bits.len() != 3 || !bits[2].starts_with("HTTP/1.") {
+        return Err(error(400, "malformed request line"));
+    }
+    let method = bits[0].to_string();
+    let (path, query) = match bits[1].split_once('?') {
+        Some((p, q)) => (p.to_string(), q),
+        None => (bits[1].to_string(), ""),
+    };
+    let query = query
+        .split('&')
+        .filter(|q| !q.is_empty())
+        .map(|q| match q.split_once('=') {
+            Some((k, v)) => (k.to_string(), v.to_string()),
+            None => (q.to_string(), String::new()),
+        })
+        .collect();
+
+    // headers; we only care about the length of the body.
+    let mut length = 0;
+    loop {
+        let header = read_header_line(&mut reader)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>()
+                    .map_err(|_| error(400, "invalid Content-Length"))?;
+            }
+        }
+    }
+    if length > MAX_BODY {
+        return Err(error(413, "the request body is too large"));
+    }
+    let mut body = vec![0; length];
+    reader.into_inner().read_exact(&mut body).map_err(read_error)?;
+    Ok(Request {method, path, query, body})
+}
+
+
+fn list_days() -> Response {
+    let solvers = registry::SOLVERS
+        .iter()
+        .map(|s| json::object(vec![
+            ("day", s.day.into()),
+            ("part", s.part.into()),
+            ("solver", s.name.into()),
+            ("primary", (registry::solvers_for(s.day, s.part)[0].name == s.name).into()),
+            ("title", registry::DAYS.iter().find(|d| d.day == s.day).map(|d| d.title).into()),
+        ]))
+        .collect::<Vec<_>>();
+    (200, Value::Array(solvers))
+}
+
+
+fn solve(request: &Request, day: &str, part: &str) -> Response {
+    let (day, part) = match (day.parse::<u32>(), part.parse::<u32>()) {
+        (Ok(day), Ok(part)) => (day, part),
+        _ => return error(404, "day and part must be numbers"),
+    };
+    let solvers = registry::solvers_for(day, part);
+    let solver = match request.query.iter().find(|(k, _)| k == "solver") {
+        Some((_, name)) => solvers.iter().find(|s| s.name == name),
+        None => solvers.first(),
+    };
+    let solver = match solver {
+        Some(solver) => *solver,
+        None => return error(404, &format!("no solver for {}-{}", day, part)),
+    };
+    let input = match std::str::from_utf8(&request.body) {
+        Ok(input) => input,
+        Err(_) => return error(400, "the puzzle input must be UTF-8"),
+    };
+    SOLVING.with(|solving| solving.set(true));
+    let solution = panic::catch_unwind(|| (solver.solve)(input, &Context::quiet()));
+    SOLVING.with(|solving| solving.set(false));
+    match solution {
+        Ok(Ok(solution)) => (200, explain::to_json(solver, &solution, true)),
+        Ok(Err(e @ SolveError::ParseError(_))) => error(422, &e.to_string()),
+        Ok(Err(e @ SolveError::NoAnswer(_))) => error(422, &e.to_string()),
+        Err(payload) => error(500, &format!("solver panicked: {}", utils::panic_message(payload))),
+    }
+}
+
+
+fn route(request: &Request) -> Response {
+    let segments = request.path.trim_matches('/').split('/').collect::<Vec<_>>();
+    match segments.as_slice() {
+        ["days"] => match request.method.as_str() {
+            "GET" => list_days(),
+            _ => error(405, "use GET /days"),
+        },
+        ["solve", day, part] => match request.method.as_str() {
+            "POST" => solve(request, day, part),
+            _ => error(405, "use POST /solve/{day}/{part} with the input as the body"),
+        },
+        _ => error(404, &format!("no such endpoint: {}", request.path)),
+    }
+}
+
+
+fn handle(mut stream: TcpStream) {
+    if let Err(e) = stream.set_read_timeout(Some(READ_TIMEOUT)) {
+        eprintln!("couldn't set a timeout on the connection: {}", e);
+        return;
+    }
+    let (status, body) = match read_request(&stream) {
+        Ok(request) => {
+            let response = route(&request);
+            println!("{} {} -> {}", request.method, request.path, response.0);
+            response
+        },
+        Err(response) => response,
+    };
+    let body = body.to_string();
+    let response = format!("HTTP/1.1 {} {}\r\nContent-Type: application/json\r\n\
+                            Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                           status, reason(status), body.len(), body);
+    if let Err(e) = stream.write_all(response.as_bytes()) {
+        eprintln!("couldn't send the response: {}", e);
+    }
+    // closing with some of a refused request still unread would reset the connection, which can
+    // lose the response before the client reads it; so read (a little more of) it first.
+    stream.shutdown(Shutdown::Write).ok();
+    io::copy(&mut (&stream).take(MAX_HEADER), &mut io::sink()).ok();
+}
+
+
+/// Serve the solvers on localhost:port until the process is killed.
+pub fn serve(port: u16) -> Result<(), String> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("couldn't listen on port {}: {}", port, e))?;
+    println!("Serving the solvers on http://127.0.0.1:{}/", port);
+    // a panicking solver is reported as a 500, so don't also dump it to stderr; any other panic
+    // is a bug in the server, and is reported as usual.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !SOLVING.with(|solving| solving.get()) {
+            hook(info);
+        }
+    }));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                thread::spawn(move || handle(stream));
+            },
+            Err(e) => eprintln!("connection failed: {}", e),
+        }
+    }
+    Ok(())
+}
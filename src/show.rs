@@ -0,0 +1,23 @@
+// Show a day's puzzle offline, or one of the examples from it.
+
+
+use crate::puzzles;
+
+
+/// Print the description of a day's puzzle or, with an example name, just that example's input
+/// (e.g. to save it to a file).
+pub fn show(day: u32, example: Option<&str>) -> Result<(), String> {
+    let description = puzzles::description(day)
+        .ok_or(format!("There's no description of day {} (yet?)", day))?;
+    match example {
+        None => print!("{}", description),
+        Some(name) => {
+            let input = puzzles::example(day, name).ok_or_else(|| {
+                let names = puzzles::examples(day).iter().map(|(n, _)| *n).collect::<Vec<_>>();
+                format!("Day {} has no example '{}'; it has: {}", day, name, names.join(", "))
+            })?;
+            print!("{}", input);
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// Run a day-part's streaming solver, which reads its input a line at a time, on a file or on
+// stdin.  This is for inputs too big to read all at once, e.g. generated ones:
+//
+//     generate-passwords | aoc2020 stream 2-1 --input -
+
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::time::Instant;
+
+use crate::context::Context;
+use crate::inputs;
+use crate::registry;
+
+
+/// Stream an input (by default the input of the day-part's primary solver; '-' for stdin) through
+/// the day-part's streaming solver and print the answer.
+pub fn stream(day: u32, part: u32, input: Option<&str>) -> Result<(), String> {
+    let streamer = registry::streamer_for(day, part).ok_or_else(|| {
+        let streaming = registry::STREAMERS
+            .iter()
+            .map(|s| format!("{}-{}", s.day, s.part))
+            .collect::<Vec<_>>();
+        format!("Day {}-{} has no streaming solver (try one of {})",
+                day, part, streaming.join(", "))
+    })?;
+    let file_name = match input {
+        Some(file_name) => file_name,
+        None => registry::solvers_for(day, part)
+            .first()
+            .map(|s| s.input)
+            .ok_or(format!("Day {}-{} has no solver to take the input from", day, part))?,
+    };
+
+    // a file on disk is read as it goes; only an embedded input is already in memory
+    let mut reader: Box<dyn BufRead> = match file_name {
+        "-" => Box::new(io::stdin().lock()),
+        _ => match File::open(file_name) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => match inputs::embedded(file_name) {
+                Some(contents) if e.kind() == io::ErrorKind::NotFound => {
+                    Box::new(contents.as_bytes())
+                },
+                _ => return Err(format!("couldn't read {}: {}", file_name, e)),
+            },
+        },
+    };
+    let source = if file_name == "-" { "stdin" } else { file_name };
+
+    let start = Instant::now();
+    let solution = (streamer.solve)(source, &mut reader, &Context::quiet())
+        .map_err(|e| e.to_string())?;
+    println!("{}", solution.answer);
+    eprintln!("{}-{} {} (streaming) took {:.3}s", day, part, streamer.name,
+              start.elapsed().as_secs_f64());
+    Ok(())
+}
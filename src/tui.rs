@@ -0,0 +1,382 @@
+// A full-screen dashboard of all 25 days.
+//
+// The days are laid out in a grid with a star for each part we have the verified answer for.  Move
+// around the grid with the arrow keys (or hjkl), pick the part with tab (or 1 and 2), and press
+// enter to run the primary solver; the side panel shows the time ticking over while it runs and
+// then the answer.  'e' switches between the real inputs and the examples from the puzzles.
+//
+// There are no dependencies for this: the terminal is put into raw mode with stty and drawn on
+// with ANSI escape codes.  Keys are read on one thread and solvers run on another, and both report
+// back to the main loop through a channel so that the screen can be redrawn while waiting.
+
+
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Read, Write};
+use std::panic;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answers::{self, Expected};
+use crate::context::Context;
+use crate::inputs;
+use crate::registry::{self, DAYS};
+use crate::runner::{self, Verification};
+use crate::utils;
+
+
+const COLUMNS: u32 = 5;
+const LAST_DAY: u32 = 25;
+const CELL: u32 = 11;  // the width of a day in the grid
+const PANEL: u32 = 60;  // the column the side panel starts in
+const TICK: Duration = Duration::from_millis(50);
+
+
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Tab,
+    Enter,
+    Char(char),
+}
+
+
+// what the main loop waits for
+enum Event {
+    Key(Key),
+    Done(Outcome),
+}
+
+
+struct Outcome {
+    day: u32,
+    part: u32,
+    example: bool,
+    answer: Result<String, String>,
+    elapsed: Duration,
+}
+
+
+// Puts the terminal into raw mode on the alternate screen, and puts it back again when dropped, so
+// that the terminal is restored however the dashboard exits.
+struct Terminal {
+    saved: String,
+}
+
+
+impl Terminal {
+    fn stty(args: &[&str]) -> Result<String, String> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(Stdio::inherit())
+            .output()
+            .map_err(|e| format!("couldn't run stty: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("stty failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn enter() -> Result<Terminal, String> {
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            return Err("the dashboard needs a terminal".to_string());
+        }
+        let saved = Terminal::stty(&["-g"])?;
+        Terminal::stty(&["raw", "-echo"])?;
+        // alternate screen, hide the cursor
+        print!("\x1b[?1049h\x1b[?25l");
+        let _ = io::stdout().flush();
+        Ok(Terminal { saved })
+    }
+}
+
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = Terminal::stty(&[&self.saved]);
+    }
+}
+
+
+// Read keys from stdin until it closes; escape sequences for the arrow keys arrive in one read.
+fn read_keys(events: Sender<Event>) {
+    let mut stdin = io::stdin();
+    let mut buf = [0u8; 16];
+    loop {
+        let n = match stdin.read(&mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(n) => n,
+        };
+        let key = match &buf[..n] {
+            [0x1b, b'[', b'A', ..] => Key::Up,
+            [0x1b, b'[', b'B', ..] => Key::Down,
+            [0x1b, b'[', b'C', ..] => Key::Right,
+            [0x1b, b'[', b'D', ..] => Key::Left,
+            [b'\t', ..] => Key::Tab,
+            [b'\r', ..] | [b'\n', ..] => Key::Enter,
+            // ctrl-c doesn't raise a signal in raw mode
+            [3, ..] => Key::Char('q'),
+            [c, ..] if c.is_ascii() => Key::Char(*c as char),
+            _ => continue,
+        };
+        if events.send(Event::Key(key)).is_err() {
+            return;
+        }
+    }
+}
+
+
+// Run the primary solver for a day-part, timing just the solver.
+fn solve(day: u32, part: u32, example: bool) -> (Result<String, String>, Duration) {
+    let input = match read_input(day, part, example) {
+        Ok(input) => input,
+        Err(e) => return (Err(e), Duration::default()),
+    };
+    let solver = registry::solvers_for(day, part)[0];
+    let start = Instant::now();
+    let outcome = panic::catch_unwind(|| (solver.solve)(&input, &Context::quiet()));
+    let elapsed = start.elapsed();
+    let answer = match outcome {
+        Ok(Ok(solution)) => Ok(solution.answer),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(payload) => Err(format!("panicked: {}", utils::panic_message(payload))),
+    };
+    (answer, elapsed)
+}
+
+
+// Run a day-part on a thread, and send the outcome back.
+fn start_run(day: u32, part: u32, example: bool, events: Sender<Event>) {
+    thread::spawn(move || {
+        let (answer, elapsed) = solve(day, part, example);
+        let _ = events.send(Event::Done(Outcome { day, part, example, answer, elapsed }));
+    });
+}
+
+
+// where the input for a run comes from: the input file, or the example's name in the puzzle.
+fn input_name(day: u32, part: u32, example: bool) -> Option<String> {
+    if example {
+        registry::example_for(day, part).map(|e| format!("example '{}'", e.name))
+    } else {
+        registry::solvers_for(day, part).first().map(|s| s.input.to_string())
+    }
+}
+
+
+fn read_input(day: u32, part: u32, example: bool) -> Result<String, String> {
+    if example {
+        let example = registry::example_for(day, part).ok_or("no example")?;
+        example.input()
+            .map(|input| input.to_string())
+            .ok_or(format!("puzzles/day{}.md has no example '{}'", day, example.name))
+    } else {
+        let file_name = registry::solvers_for(day, part)
+            .first()
+            .map(|s| s.input)
+            .ok_or("no input")?;
+        inputs::read(file_name)
+            .map_err(|e| format!("couldn't read {}: {}", file_name, e))
+    }
+}
+
+
+struct Dashboard {
+    day: u32,
+    part: u32,
+    example: bool,
+    expected: Vec<Expected>,
+    // the last run of each day-part, on the real input or on the example
+    outcomes: HashMap<(u32, u32, bool), Outcome>,
+    running: Option<(u32, u32, bool, Instant)>,
+}
+
+
+impl Dashboard {
+    fn verification(&self, outcome: &Outcome) -> Verification {
+        let answer = match &outcome.answer {
+            Ok(answer) => answer,
+            Err(_) => return Verification::Unverified,
+        };
+        if outcome.example {
+            match registry::example_for(outcome.day, outcome.part) {
+                Some(e) if e.answer == answer => Verification::Correct,
+                Some(e) => Verification::Wrong(e.answer.to_string()),
+                None => Verification::Unverified,
+            }
+        } else {
+            runner::verify(outcome.day, outcome.part, answer, &self.expected)
+        }
+    }
+
+    // '*' for a part we have the star for, 'x' if its last run here failed or was wrong, 'o' for
+    // a part with a solver but no verified answer yet, and '.' for a part with no solver.
+    fn star(&self, day: u32, part: u32) -> String {
+        if let Some(outcome) = self.outcomes.get(&(day, part, false)) {
+            if outcome.answer.is_err() || matches!(self.verification(outcome), Verification::Wrong(_)) {
+                return "\x1b[31mx\x1b[39m".to_string();
+            }
+        }
+        if self.expected.iter().any(|e| e.day == day && e.part == part) {
+            "\x1b[33;1m*\x1b[22;39m".to_string()
+        } else if !registry::solvers_for(day, part).is_empty() {
+            "o".to_string()
+        } else {
+            "\x1b[2m.\x1b[22m".to_string()
+        }
+    }
+
+    // e.g. " 4  * * ", with the selected part in brackets if the day is selected
+    fn cell(&self, day: u32) -> String {
+        let (star1, star2) = (self.star(day, 1), self.star(day, 2));
+        if day != self.day {
+            return format!(" {:>2}  {}  {} ", day, star1, star2);
+        }
+        let (part1, part2) = if self.part == 1 {
+            (format!("[{}]", star1), format!(" {} ", star2))
+        } else {
+            (format!(" {} ", star1), format!("[{}]", star2))
+        };
+        format!("\x1b[7m {:>2} {}{}\x1b[27m", day, part1, part2)
+    }
+
+    fn panel(&self) -> Vec<String> {
+        let (day, part) = (self.day, self.part);
+        let title = DAYS.iter().find(|d| d.day == day).map(|d| d.title).unwrap_or("");
+        let mut lines = vec![format!("\x1b[1mDay {} part {}\x1b[22m  {}", day, part, title), String::new()];
+        let solver = match registry::solvers_for(day, part).first() {
+            Some(solver) => *solver,
+            None => {
+                lines.push("no solver yet".to_string());
+                return lines;
+            },
+        };
+        lines.push(format!("solver: {} (v{})", solver.name, solver.version));
+        match input_name(day, part, self.example) {
+            Some(name) => lines.push(format!("input:  {}", name)),
+            None => lines.push("input:  no example for this part".to_string()),
+        }
+        lines.push(String::new());
+        match (&self.running, self.outcomes.get(&(day, part, self.example))) {
+            (Some((d, p, e, start)), _) if (*d, *p, *e) == (day, part, self.example) => {
+                lines.push(format!("running... {:.3}s", start.elapsed().as_secs_f64()));
+            },
+            (_, Some(outcome)) => {
+                lines.push(format!("time:   {:.3} ms", outcome.elapsed.as_secs_f64() * 1000.0));
+                match &outcome.answer {
+                    Ok(answer) => {
+                        lines.push(format!("answer: \x1b[1m{}\x1b[22m", answer));
+                        lines.push(match self.verification(outcome) {
+                            Verification::Correct => "\x1b[32mcorrect\x1b[39m".to_string(),
+                            v @ Verification::Wrong(_) => format!("\x1b[31m{}\x1b[39m", v),
+                            v => v.to_string(),
+                        });
+                    },
+                    Err(e) => lines.push(format!("\x1b[31m{}\x1b[39m", e)),
+                }
+            },
+            _ => lines.push("press enter to run".to_string()),
+        }
+        lines
+    }
+
+    fn draw(&self) {
+        let mut screen = String::from("\x1b[2J\x1b[H");
+        let inputs = if self.example { "examples" } else { "real inputs" };
+        screen.push_str(&format!("\x1b[1;2H\x1b[1mAdvent of Code 2020\x1b[22m  ({})", inputs));
+        for day in 1..=LAST_DAY {
+            let (row, column) = ((day - 1) / COLUMNS, (day - 1) % COLUMNS);
+            screen.push_str(&format!("\x1b[{};{}H{}", 3 + row * 2, 2 + column * CELL, self.cell(day)));
+        }
+        for (i, line) in self.panel().iter().enumerate() {
+            screen.push_str(&format!("\x1b[{};{}H{}", 3 + i as u32, PANEL, line));
+        }
+        screen.push_str("\x1b[14;2H* star  o unverified  x failed  . no solver");
+        screen.push_str("\x1b[15;2Harrows/hjkl move  tab/1/2 part  enter run  e examples/real  q quit");
+        print!("{}", screen);
+        let _ = io::stdout().flush();
+    }
+
+    fn move_by(&mut self, delta: i32) {
+        let day = self.day as i32 + delta;
+        if day >= 1 && day <= LAST_DAY as i32 {
+            self.day = day as u32;
+        }
+    }
+
+    // returns false to quit
+    fn key(&mut self, key: Key, events: &Sender<Event>) -> bool {
+        match key {
+            Key::Up | Key::Char('k') => self.move_by(-(COLUMNS as i32)),
+            Key::Down | Key::Char('j') => self.move_by(COLUMNS as i32),
+            Key::Left | Key::Char('h') => self.move_by(-1),
+            Key::Right | Key::Char('l') => self.move_by(1),
+            Key::Tab => self.part = 3 - self.part,
+            Key::Char('1') => self.part = 1,
+            Key::Char('2') => self.part = 2,
+            Key::Char('e') => self.example = !self.example,
+            Key::Char('q') => return false,
+            Key::Enter => {
+                let runnable = !registry::solvers_for(self.day, self.part).is_empty()
+                    && input_name(self.day, self.part, self.example).is_some();
+                if self.running.is_none() && runnable {
+                    self.running = Some((self.day, self.part, self.example, Instant::now()));
+                    start_run(self.day, self.part, self.example, events.clone());
+                }
+            },
+            Key::Char(_) => {},
+        }
+        true
+    }
+
+    fn run(&mut self, events: &Sender<Event>, incoming: &Receiver<Event>) {
+        loop {
+            self.draw();
+            match incoming.recv_timeout(TICK) {
+                Ok(Event::Key(key)) => {
+                    if !self.key(key, events) {
+                        return;
+                    }
+                },
+                Ok(Event::Done(outcome)) => {
+                    self.running = None;
+                    self.outcomes.insert((outcome.day, outcome.part, outcome.example), outcome);
+                },
+                Err(RecvTimeoutError::Timeout) => {},
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
+
+
+/// Show the dashboard until 'q' is pressed.
+pub fn tui() -> Result<(), String> {
+    let expected = answers::read_expected(answers::ANSWERS_FILE)?;
+    let terminal = Terminal::enter()?;
+    // a panicking solver is shown in the panel; don't let the default hook scribble on the screen
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let (events, incoming) = mpsc::channel();
+    let keys = events.clone();
+    thread::spawn(move || read_keys(keys));
+    let mut dashboard = Dashboard {
+        day: 1,
+        part: 1,
+        example: false,
+        expected,
+        outcomes: HashMap::new(),
+        running: None,
+    };
+    dashboard.run(&events, &incoming);
+
+    panic::set_hook(hook);
+    drop(terminal);
+    Ok(())
+}
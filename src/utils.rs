@@ -1,40 +1,343 @@
 // handy utilities
 
 
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
+use std::time::Duration;
 
+use thiserror::Error;
 
-/// Read lines from a file and parse them into a vector; blows up if the file is not found.
-/// The vector is a vector of Results.
+use crate::inputs;
+use crate::json::Value;
+use crate::registry::{BadLine, Checked, SolveError};
+
+
+/// Why an input file couldn't be read into a vector: the file couldn't be read, or one of its
+/// lines (1-based) didn't parse, or the input as a whole doesn't make sense (e.g. the rows of a
+/// map aren't all the same width).
+#[derive(Error, Debug, Clone)]
+pub enum InputError {
+    #[error("couldn't read {path}: {error}")]
+    Unreadable { path: String, error: String },
+    #[error("{path}:{line}: {error}: '{text}'")]
+    Unparsable { path: String, line: usize, text: String, error: String },
+    #[error("{path}: {error}")]
+    Invalid { path: String, error: String },
+}
+
+
+impl From<InputError> for SolveError {
+    fn from(e: InputError) -> Self {
+        SolveError::ParseError(e.to_string())
+    }
+}
+
+
+/// The lines of a reader, parsed one at a time as they are read; see stream_lines.
+pub struct StreamLines<T, R> {
+    source: String,
+    reader: R,
+    line: usize,
+    buffer: String,
+    done: bool,
+    parsed: PhantomData<T>,
+}
+
+
+impl<T, R> Iterator for StreamLines<T, R>
+    where T: FromStr,
+          T::Err: fmt::Display,
+          R: BufRead,
+{
+    type Item = Result<T, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.buffer.clear();
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => {
+                self.done = true;
+                None
+            },
+            Ok(_) => {
+                self.line += 1;
+                let line = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                Some(line.parse::<T>().map_err(|e| InputError::Unparsable {
+                    path: self.source.clone(),
+                    line: self.line,
+                    text: line.to_string(),
+                    error: e.to_string(),
+                }))
+            },
+            // there's no carrying on after the reader fails
+            Err(e) => {
+                self.done = true;
+                let path = self.source.clone();
+                Some(Err(InputError::Unreadable { path, error: e.to_string() }))
+            },
+        }
+    }
+}
+
+
+/// Parse the lines of any reader (a file, stdin, a string's bytes) lazily, one line at a time, so
+/// that only the line being parsed is ever in memory.  `source` names the input in errors.
+/// Use like
+///     for thing in stream_lines::<Thing, _>("stdin", io::stdin().lock()) { ... }
+pub fn stream_lines<T, R>(source: &str, reader: R) -> StreamLines<T, R>
+    where T: FromStr,
+          T::Err: fmt::Display,
+          R: BufRead,
+{
+    StreamLines {
+        source: source.to_string(),
+        reader,
+        line: 0,
+        buffer: String::new(),
+        done: false,
+        parsed: PhantomData,
+    }
+}
+
+
+/// Read the whole of an input file (or the embedded copy of it), for inputs that aren't a thing
+/// per line.
+pub fn read_input(file_name: &str) -> Result<String, InputError> {
+    inputs::read(file_name).map_err(
+        |e| InputError::Unreadable { path: file_name.to_string(), error: e.to_string() })
+}
+
+
+// read a file and parse each of its lines, keeping the failures along with the lines that parsed.
+fn read_lines<T>(file_name: &str) -> Result<Vec<Result<T, InputError>>, InputError>
+    where T: FromStr,
+          T::Err: fmt::Display,
+{
+    let data = read_input(file_name)?;
+    Ok(stream_lines(file_name, data.as_bytes()).collect())
+}
+
+
+/// Read lines from a file and parse them into a vector, stopping at the first line that doesn't
+/// parse.  The error says which file and line were at fault, and why.
 /// Use like
-///     let things = read_file::<Thing>("filename");
-pub fn read_file<T: FromStr>(file_name: &str) -> Vec<Result<T, <T as FromStr>::Err>> {
-    std::fs::read_to_string(file_name)
-        .expect("file not found!")
+///     let things = read_file::<Thing>("filename")?;
+pub fn read_file<T>(file_name: &str) -> Result<Vec<T>, InputError>
+    where T: FromStr,
+          T::Err: fmt::Display,
+{
+    read_lines(file_name)?.into_iter().collect()
+}
+
+
+/// Read lines from a file and parse them like read_file, but carry on past lines that don't
+/// parse: returns the lines that did along with every failure.  It's only an error if the file
+/// can't be read at all.
+pub fn read_file_lenient<T>(file_name: &str) -> Result<(Vec<T>, Vec<InputError>), InputError>
+    where T: FromStr,
+          T::Err: fmt::Display,
+{
+    let mut parsed = Vec::new();
+    let mut failures = Vec::new();
+    for line in read_lines(file_name)? {
+        match line {
+            Ok(thing) => parsed.push(thing),
+            Err(e) => failures.push(e),
+        }
+    }
+    Ok((parsed, failures))
+}
+
+
+/// Parse every line of some input into a vector, stopping at the first line that doesn't parse.
+/// The error says which line (1-based) was at fault.
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, SolveError>
+    where T: FromStr,
+          T::Err: fmt::Display,
+{
+    input
         .lines()
-        .map(|x| x.parse())
+        .enumerate()
+        .map(|(n, line)| line.parse::<T>().map_err(
+            |e| SolveError::ParseError(format!("line {}: {}: '{}'", n + 1, e, line))))
         .collect()
 }
 
 
+/// Parse every line of some input, collecting every line that doesn't parse rather than stopping
+/// at the first.  These are the lines that parse_lines() parses, so a blank line is checked (and
+/// most likely bad) like any other.
+pub fn check_lines<T>(input: &str) -> Checked
+    where T: FromStr,
+          T::Err: fmt::Display,
+{
+    let lines = input.lines().enumerate().collect::<Vec<_>>();
+    let bad = lines
+        .iter()
+        .filter_map(|(n, line)| line.parse::<T>().err().map(
+            |e| BadLine { line: n + 1, text: line.to_string(), error: e.to_string() }))
+        .collect();
+    Checked { checked: lines.len(), bad }
+}
+
 
-/// process a set of lines into a <Vec<Vec<&str>> -- i.e. don't copy the lines, just their
-/// references.  We split batches on blank lines.
-pub fn process_lines_to_batches<'a>(lines: &[&'a str]) -> Vec<Vec<&'a str>> {
+/// Split input into batches of lines on blank lines, with the (1-based) line number that each
+/// batch starts on.
+pub fn numbered_batches(input: &str) -> Vec<(usize, Vec<&str>)> {
     let mut result = Vec::new();
-    let mut batch = Vec::new();
-    for line in lines {
+    let mut batch: Vec<&str> = Vec::new();
+    let mut start = 0;
+    for (n, line) in input.lines().enumerate() {
         if line.trim().is_empty() {
             if !batch.is_empty() {
-                result.push(batch.clone());
+                result.push((start, batch.clone()));
                 batch.clear();
             }
         } else {
-            batch.push(*line);
+            if batch.is_empty() {
+                start = n + 1;
+            }
+            batch.push(line);
         }
     }
     if !batch.is_empty() {
-        result.push(batch.clone());
+        result.push((start, batch));
     }
     result
 }
+
+
+/// A record that is written over a batch of lines, as in the inputs where the records are
+/// separated by blank lines (passports, groups of answers).  The lines are never blank.
+pub trait FromBatch: Sized {
+    type Err;
+
+    fn from_batch(lines: &[&str]) -> Result<Self, Self::Err>;
+}
+
+
+/// Parse each blank-line-separated batch of an input into a T, with the (1-based) lines that it
+/// came from so that an error can say where the batch is.  Lines that are only whitespace count
+/// as blank.
+/// Use like
+///     for (lines, thing) in read_batches::<Thing>(input) { ... }
+pub fn read_batches<T: FromBatch>(input: &str)
+    -> impl Iterator<Item = (RangeInclusive<usize>, Result<T, T::Err>)> + '_
+{
+    numbered_batches(input)
+        .into_iter()
+        .map(|(start, batch)| (start..=start + batch.len() - 1, T::from_batch(&batch)))
+}
+
+
+/// The whitespace-separated parts of a batch's lines as one line, for records whose parts can be
+/// spread over their lines in any way (e.g. a passport's 'key:value' parts).
+pub fn join_batch(lines: &[&str]) -> String {
+    lines.iter().flat_map(|l| l.split_whitespace()).collect::<Vec<_>>().join(" ")
+}
+
+
+/// Where a batch is in its input, for errors: 'line 3' or 'lines 3-6'.
+pub fn line_range(lines: &RangeInclusive<usize>) -> String {
+    if lines.start() == lines.end() {
+        format!("line {}", lines.start())
+    } else {
+        format!("lines {}-{}", lines.start(), lines.end())
+    }
+}
+
+
+/// How many times each value occurs, as an object keyed by the values in order.
+pub fn histogram<K: Ord + fmt::Display>(values: impl IntoIterator<Item = K>) -> Value {
+    let mut counts = BTreeMap::new();
+    for value in values {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    Value::Object(counts.into_iter().map(|(k, n): (K, usize)| (k.to_string(), n.into())).collect())
+}
+
+
+/// The count, number of distinct values, and smallest and largest of some numbers, for describing
+/// an input; a day can add its own statistics to these.
+pub fn number_stats<T: Copy + Ord + Into<Value>>(numbers: &[T]) -> Vec<(&'static str, Value)> {
+    vec![
+        ("numbers", numbers.len().into()),
+        ("distinct", numbers.iter().collect::<BTreeSet<_>>().len().into()),
+        ("min", numbers.iter().min().copied().into()),
+        ("max", numbers.iter().max().copied().into()),
+    ]
+}
+
+
+/// The median of a set of timings; zero if there aren't any.
+pub fn median(timings: &[Duration]) -> Duration {
+    let mut sorted = timings.to_vec();
+    sorted.sort();
+    sorted.get(sorted.len() / 2).cloned().unwrap_or_default()
+}
+
+
+/// A (FNV-1a) hash of some input so that we can tell whether two inputs are the same.
+pub fn hash_input(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+
+/// Format seconds since the Unix epoch as a UTC date and time, e.g. "2020-12-01 05:00:00 UTC".
+pub fn format_timestamp(secs: u64) -> String {
+    // convert days since the epoch into a civil date (Howard Hinnant's algorithm)
+    let days = (secs / 86400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    let rem = secs % 86400;
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            year, month, day, rem / 3600, (rem / 60) % 60, rem % 60)
+}
+
+
+/// Seconds since the Unix epoch, now.
+pub fn now_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+
+/// The pct'th percentile (nearest rank) of a set of timings; zero if there aren't any.
+pub fn percentile(timings: &[Duration], pct: f64) -> Duration {
+    let mut sorted = timings.to_vec();
+    sorted.sort();
+    if sorted.is_empty() {
+        return Duration::default();
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+
+/// Turn a panic payload into something printable.
+pub fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
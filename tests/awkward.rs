@@ -0,0 +1,63 @@
+// Run the solvers on inputs that parse but are awkward: numbers big enough to overflow a sum,
+// entries that would only add up if used twice, and programs that can't be fixed.  Each should
+// give an answer or say there isn't one, rather than panic.
+
+use aoc2020::context::Context;
+use aoc2020::registry;
+
+
+// the answer from every solver for the day-part, as text or the error
+fn answers(day: u32, part: u32, input: &str) -> Vec<Result<String, String>> {
+    registry::solvers_for(day, part)
+        .into_iter()
+        .map(|solver| (solver.solve)(input, &Context::quiet())
+             .map(|s| s.answer)
+             .map_err(|e| e.to_string()))
+        .collect()
+}
+
+
+fn assert_all(day: u32, part: u32, input: &str, expected: Result<&str, &str>) {
+    for answer in answers(day, part, input) {
+        assert!(answer.as_deref() == expected.map_err(|e| e.to_string()).as_deref(),
+                "{}-{} on {:?}: expected {:?}, got {:?}", day, part, input, expected, answer);
+    }
+}
+
+
+#[test]
+fn expense_entries_too_big_to_add_are_skipped() {
+    assert_all(1, 1, "4294967295\n1\n2019\n", Ok("2019"));
+    assert_all(1, 2, "4294967295\n1\n4294967295\n1\n2018\n", Ok("2018"));
+}
+
+
+#[test]
+fn an_expense_entry_is_only_used_once() {
+    assert_all(1, 1, "1010\n7\n", Err("no answer found: Couldn't find a pair that adds to 2020"));
+    assert_all(1, 2, "505\n1010\n7\n",
+               Err("no answer found: Couldn't find a triple that adds to 2020"));
+}
+
+
+#[test]
+fn numbers_too_big_to_add_are_not_sums() {
+    let input = "18446744073709551615\n".repeat(26);
+    assert_all(9, 1, &input, Ok("18446744073709551615"));
+    assert_all(9, 2, &input, Err("no answer found: no sequence adds up to 18446744073709551615"));
+    for streamer in registry::STREAMERS.iter().filter(|s| (s.day, s.part) == (9, 1)) {
+        let answer = (streamer.solve)("big", &mut input.as_bytes(), &Context::quiet())
+            .map(|s| s.answer);
+        assert_eq!(answer.ok().as_deref(), Some("18446744073709551615"));
+    }
+}
+
+
+#[test]
+fn programs_that_run_off_the_end_have_no_answer() {
+    let no_patch = "no answer found: no single patch makes the program finish";
+    assert_all(8, 1, "acc +1\nacc +1\n", Err("no answer found: the program never loops"));
+    assert_all(8, 2, "acc +1\nacc +1\n", Err(no_patch));
+    // the only patch that escapes the loop jumps out of the program instead
+    assert_all(8, 2, "nop -5\njmp -1\njmp -2\n", Err(no_patch));
+}
@@ -0,0 +1,90 @@
+// 'aoc2020 batch' reads a manifest of jobs, reporting every bad line in it, and prints a table of
+// the results in manifest order however many jobs run at once, with the slow ones timed out.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+
+// a directory for the manifest and the inputs, named for the test that uses it
+fn batch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc2020-batch-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+
+fn batch(dir: &Path, manifest: &str, jobs: usize) -> Output {
+    fs::write(dir.join("manifest.txt"), manifest).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .args(["batch", "manifest.txt", "--jobs", &jobs.to_string()])
+        .current_dir(dir)
+        .output()
+        .expect("couldn't run aoc2020")
+}
+
+
+#[test]
+fn every_bad_line_in_the_manifest_is_reported() {
+    let dir = batch_dir("manifest");
+    let manifest = "\
+# a comment, then a blank line
+
+1-1 report.txt expect=514579 timeout=2s
+x-1 report.txt
+1-1
+1-1 report.txt colour=blue
+1-1 report.txt timeout=soon
+25-1 report.txt
+";
+    let output = batch(&dir, manifest, 1);
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(!output.status.success(), "expected the batch to fail");
+    assert!(output.stdout.is_empty(), "expected no jobs to run, got {:?}",
+            String::from_utf8_lossy(&output.stdout));
+    assert_eq!(String::from_utf8_lossy(&output.stderr).lines().collect::<Vec<_>>(), vec![
+        "manifest.txt:4: corrupted job line: bad day-part 'x-1'",
+        "manifest.txt:5: corrupted job line: needs a day-part and an input: 1-1",
+        "manifest.txt:6: corrupted job line: unknown option 'colour=blue'",
+        "manifest.txt:7: corrupted job line: bad timeout 'soon'",
+        "manifest.txt:8: Day 25-1 not defined (yet?)",
+    ]);
+}
+
+
+#[test]
+fn results_are_in_manifest_order_and_slow_jobs_time_out() {
+    let dir = batch_dir("jobs");
+    fs::write(dir.join("report.txt"), "1721\n979\n366\n299\n675\n1456\n").unwrap();
+    // no three of these add up to 2020, so the naive 1-2 tries all ~4.5 billion triples
+    let slow = (1000..4000).map(|n| format!("{}\n", n)).collect::<String>();
+    fs::write(dir.join("slow.txt"), slow).unwrap();
+    let manifest = "\
+1-2 slow.txt timeout=200ms
+1-1 report.txt expect=514579
+1-2 report.txt expect=1
+1-1 missing.txt
+1-1 report.txt
+";
+    let output = batch(&dir, manifest, 3);
+    fs::remove_dir_all(&dir).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "expected the batch to fail");
+    let rows = stdout
+        .lines()
+        .skip(2)
+        .take_while(|l| !l.is_empty())
+        .map(|l| l.split_whitespace().take(4).collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>();
+    assert_eq!(rows, vec![
+        "1 1-2 slow.txt TIMEOUT",
+        "2 1-1 report.txt ok",
+        "3 1-2 report.txt WRONG",
+        "4 1-1 missing.txt UNREADABLE",
+        "5 1-1 report.txt ok",
+    ], "stdout was {}", stdout);
+    assert!(stdout.contains("\n2 of 5 job(s) passed\n"), "stdout was {}", stdout);
+    assert!(stdout.contains("  line 1 (1-2 slow.txt): still running after 0.200s"),
+            "stdout was {}", stdout);
+}
@@ -0,0 +1,95 @@
+/* Exercise the C API: solve a few of the puzzle examples and check the error statuses.
+ * Prints a line for each failed check and exits non-zero if there were any. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc2020.h"
+
+static int failures = 0;
+
+
+static void check_solve(uint32_t day, uint32_t part, const char *input,
+                        int32_t expected_status, const char *expected)
+{
+    char out[256];
+    size_t out_len = sizeof(out);
+    int32_t status = aoc_solve(2020, day, part, (const uint8_t *)input, strlen(input),
+                               out, &out_len);
+    if (status != expected_status) {
+        printf("%u-%u: expected status %d, got %d (%s)\n",
+               day, part, expected_status, status, status == AOC_BUFFER_TOO_SMALL ? "" : out);
+        failures++;
+    } else if (expected != NULL && strcmp(out, expected) != 0) {
+        printf("%u-%u: expected '%s', got '%s'\n", day, part, expected, out);
+        failures++;
+    } else if (out_len != strlen(out)) {
+        printf("%u-%u: out_len is %zu but the text is %zu long\n",
+               day, part, out_len, strlen(out));
+        failures++;
+    }
+}
+
+
+static const char *EXPENSES = "1721\n979\n366\n299\n675\n1456\n";
+
+static const char *PROGRAM =
+    "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6\n";
+
+static const char *PASSPORTS =
+    "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980\nhcl:#623a2f\n\n"
+    "eyr:2029 ecl:blu cid:129 byr:1989\niyr:2014 pid:896056539 hcl:#a97842 hgt:165cm\n\n"
+    "eyr:1972 cid:100\nhcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926\n\n"
+    "hgt:59cm ecl:zzz\neyr:2038 hcl:74454a iyr:2023\npid:3556412378 byr:2007\n";
+
+
+int main(void)
+{
+    check_solve(1, 1, EXPENSES, AOC_OK, "514579");
+    check_solve(1, 2, EXPENSES, AOC_OK, "241861950");
+    check_solve(4, 2, PASSPORTS, AOC_OK, "2");
+    check_solve(8, 1, PROGRAM, AOC_OK, "5");
+    check_solve(8, 2, PROGRAM, AOC_OK, "8");
+
+    check_solve(1, 1, "1721\nnot a number\n", AOC_PARSE_ERROR, NULL);
+    check_solve(1, 1, "1\n2\n", AOC_NO_ANSWER, NULL);
+    check_solve(26, 1, EXPENSES, AOC_UNKNOWN_PUZZLE, NULL);
+
+    /* the wrong year */
+    char out[64];
+    size_t out_len = sizeof(out);
+    if (aoc_solve(2019, 1, 1, (const uint8_t *)EXPENSES, strlen(EXPENSES), out, &out_len)
+            != AOC_UNKNOWN_PUZZLE) {
+        printf("2019 1-1: expected AOC_UNKNOWN_PUZZLE\n");
+        failures++;
+    }
+
+    /* ask for the length first, then solve into a buffer of exactly the right size */
+    out_len = 0;
+    if (aoc_solve(2020, 1, 1, (const uint8_t *)EXPENSES, strlen(EXPENSES), NULL, &out_len)
+            != AOC_BUFFER_TOO_SMALL || out_len != 6) {
+        printf("1-1 with no buffer: expected AOC_BUFFER_TOO_SMALL and a length of 6, got %zu\n",
+               out_len);
+        failures++;
+    }
+    char exact[7];
+    out_len = sizeof(exact);
+    if (aoc_solve(2020, 1, 1, (const uint8_t *)EXPENSES, strlen(EXPENSES), exact, &out_len)
+            != AOC_OK || strcmp(exact, "514579") != 0) {
+        printf("1-1 into an exact buffer: expected 514579\n");
+        failures++;
+    }
+
+    /* the solver list should include the alternative solvers as well as the primaries */
+    char list[4096];
+    size_t list_len = sizeof(list);
+    if (aoc_list_solvers(list, &list_len) != AOC_OK) {
+        printf("aoc_list_solvers failed\n");
+        failures++;
+    } else if (strstr(list, "2020 1 1 pairs\n") == NULL || strstr(list, "2020 8 2 graph\n") == NULL) {
+        printf("aoc_list_solvers is missing solvers:\n%s", list);
+        failures++;
+    }
+
+    return failures == 0 ? 0 : 1;
+}
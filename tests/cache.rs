@@ -0,0 +1,54 @@
+// 'aoc2020 all' reuses a cached answer while the solver's version and its input are unchanged,
+// and computes it again once the version has been bumped.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+
+// the line 'aoc2020 all' printed for 1-1
+fn all_1_1(dir: &Path) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .arg("all")
+        .current_dir(dir)
+        .output()
+        .expect("couldn't run aoc2020");
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|l| l.starts_with("1-1 "))
+        .unwrap_or_default()
+        .to_string()
+}
+
+
+#[test]
+fn a_version_bump_invalidates_the_cached_answers() {
+    let dir = env::temp_dir().join(format!("aoc2020-cache-{}", std::process::id()));
+    fs::create_dir_all(dir.join("input")).unwrap();
+    fs::write(dir.join("input/day1-1.txt"), "1721\n979\n366\n299\n675\n1456\n").unwrap();
+    let cache = dir.join("answer-cache.jsonl");
+    let edit_cache = |from: &str, to: &str| {
+        let entries = fs::read_to_string(&cache).unwrap();
+        assert!(entries.contains(from), "no {} in the cache: {}", from, entries);
+        fs::write(&cache, entries.replace(from, to)).unwrap();
+    };
+
+    let computed = all_1_1(&dir);
+    // so a cached answer can be told from a computed one, change it
+    edit_cache(r#""answer":"514579""#, r#""answer":"999""#);
+    let cached = all_1_1(&dir);
+    // an answer from an older version of the solver is ignored, and dropped from the cache
+    edit_cache(r#""day":1,"part":1,"solver":"pairs","version":"1""#,
+               r#""day":1,"part":1,"solver":"pairs","version":"0""#);
+    let recomputed = all_1_1(&dir);
+    let entries = fs::read_to_string(&cache).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(computed.contains(" 514579 ") && !computed.contains("cached"), "got {:?}", computed);
+    assert!(cached.contains(" 999 ") && cached.contains("cached"), "got {:?}", cached);
+    assert!(recomputed.contains(" 514579 ") && !recomputed.contains("cached"),
+            "got {:?}", recomputed);
+    assert!(entries.contains(r#""solver":"pairs","version":"1""#), "cache was {}", entries);
+    assert!(!entries.contains(r#""version":"0""#), "cache was {}", entries);
+}
@@ -0,0 +1,24 @@
+// Run every day's parser check on every day's input: a day's own input should pass, and the
+// others should never have more bad lines than lines checked ('identify' relies on that).
+
+use aoc2020::{inputs, registry};
+
+
+#[test]
+fn every_check_accepts_its_own_input_and_counts_the_rest() {
+    let mut input_files = registry::SOLVERS.iter().map(|s| s.input).collect::<Vec<_>>();
+    input_files.dedup();
+    for day in registry::DAYS {
+        for file_name in &input_files {
+            let input = inputs::read(file_name).expect("couldn't read a solver's input");
+            let checked = (day.check)(&input);
+            assert!(checked.bad.len() <= checked.checked,
+                    "day {} on {}: {} bad line(s) out of {} checked",
+                    day.day, file_name, checked.bad.len(), checked.checked);
+            if Some(*file_name) == registry::default_input(day.day) {
+                assert!(checked.bad.is_empty(), "day {} rejects its own input {}: {:?}",
+                        day.day, file_name, checked.bad.first());
+            }
+        }
+    }
+}
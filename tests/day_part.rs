@@ -0,0 +1,62 @@
+// 'aoc2020 <day>-<part>' runs the walkthrough, and checks only the answer it reports for the input
+// against the answers rejected before: not the examples' answers, and not an answer that isn't
+// there.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+
+// a directory with just the given input file and rejected answers, named for the test using it
+fn run_dir(name: &str, input: (&str, &str), rejected: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc2020-day-part-{}-{}", name, std::process::id()));
+    fs::create_dir_all(dir.join("input")).unwrap();
+    fs::write(dir.join("input").join(input.0), input.1).unwrap();
+    fs::write(dir.join("rejected-answers.txt"), rejected).unwrap();
+    dir
+}
+
+
+fn run_day_part(name: &str, day_part: &str, input: (&str, &str), rejected: &str) -> Output {
+    let dir = run_dir(name, input, rejected);
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .arg(day_part)
+        .current_dir(&dir)
+        .output()
+        .expect("couldn't run aoc2020");
+    fs::remove_dir_all(&dir).unwrap();
+    output
+}
+
+
+#[test]
+fn only_the_answer_for_the_input_is_checked() {
+    // the example's answer, 514579, is higher than one that was too high, but the input's isn't
+    let output = run_day_part("example", "1-1", ("day1-1.txt", "2019\n1\n"),
+                              "1-1 10000 too-high\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr was {:?}", stderr);
+    assert!(!stderr.contains("DO NOT SUBMIT"), "stderr was {:?}", stderr);
+
+    let output = run_day_part("input", "1-1", ("day1-1.txt", "2019\n1\n"), "1-1 2019\n");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "expected 2019 to be ruled out");
+    assert!(stderr.contains("1-1: DO NOT SUBMIT 2019"), "stderr was {:?}", stderr);
+}
+
+
+#[test]
+fn no_answer_for_the_input_is_an_error() {
+    // 1000 isn't the sum of two of the 25 numbers before it, and no run of them adds up to it
+    let numbers = (1..=25).map(|n| format!("{}\n", n * 2)).collect::<String>() + "1000\n";
+    let output = run_day_part("null", "9-2", ("day9.txt", &numbers), "");
+    assert!(!output.status.success(), "expected 9-2 to fail");
+    assert_eq!(String::from_utf8_lossy(&output.stderr).trim(),
+               "Day 9-2 found no answer for the input (Sequence num)");
+
+    let output = run_day_part("none", "8-2", ("day8.txt", "acc +1\nacc +1\n"), "");
+    assert!(!output.status.success(), "expected 8-2 to fail");
+    assert_eq!(String::from_utf8_lossy(&output.stderr).trim(),
+               "Day 8-2 found no answer for the input");
+}
@@ -0,0 +1,68 @@
+// 'aoc2020 check-determinism' should stop at a run that fails, rather than compare its output
+// with the others, and shouldn't start any runs for a day-part that doesn't exist.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+
+fn check_determinism(dir: &Path, day_part: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .args(["check-determinism", day_part, "--runs", "2"])
+        .current_dir(dir)
+        .output()
+        .expect("couldn't run aoc2020")
+}
+
+
+// an empty directory to run in, named for the test that uses it
+fn empty_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc2020-determinism-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+
+// with the inputs embedded, the walkthroughs can run anywhere
+#[cfg(not(feature = "embed-inputs"))]
+#[test]
+fn a_failing_walkthrough_fails_the_check() {
+    // no input/ here, so the walkthrough fails before any process is started
+    let dir = empty_dir("no-input");
+    let output = check_determinism(&dir, "1-1");
+    fs::remove_dir(&dir).unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "expected the check to fail");
+    assert!(output.stdout.is_empty(), "expected no comparison, got {:?}",
+            String::from_utf8_lossy(&output.stdout));
+    assert_eq!(stderr.lines().next(), Some("couldn't read ./input/day1-1.txt: \
+                                            No such file or directory (os error 2)"));
+}
+
+
+#[cfg(unix)]
+#[test]
+fn a_failing_run_fails_the_check() {
+    // the inputs are here, but 'solve' fails as its answer is recorded as rejected
+    let dir = empty_dir("rejected");
+    std::os::unix::fs::symlink(Path::new(env!("CARGO_MANIFEST_DIR")).join("input"),
+                               dir.join("input")).unwrap();
+    fs::write(dir.join("rejected-answers.txt"), "1-1 121396\n").unwrap();
+    let output = check_determinism(&dir, "1-1");
+    fs::remove_dir_all(&dir).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "expected the check to fail");
+    assert!(!stdout.contains("fresh processes"), "expected no comparison, got {:?}", stdout);
+    assert!(stderr.contains("'aoc2020 solve 1-1' failed"), "stderr was {:?}", stderr);
+    assert!(stderr.contains("DO NOT SUBMIT 121396"), "stderr was {:?}", stderr);
+}
+
+
+#[test]
+fn an_unknown_day_part_is_rejected() {
+    let output = check_determinism(&env::temp_dir(), "25-1");
+    assert!(!output.status.success(), "expected the check to fail");
+    assert_eq!(String::from_utf8_lossy(&output.stderr).trim(), "Day 25-1 not defined (yet?)");
+}
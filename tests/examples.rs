@@ -0,0 +1,61 @@
+// Run every solver on the examples from the puzzle descriptions (puzzles/day<N>.md).
+
+use aoc2020::context::Context;
+use aoc2020::{puzzles, registry};
+
+
+#[test]
+fn every_example_is_in_its_puzzle() {
+    for example in registry::EXAMPLES {
+        assert!(example.input().is_some(),
+                "puzzles/day{}.md has no example '{}'", example.day, example.name);
+    }
+}
+
+
+#[test]
+fn solvers_give_the_examples_answers() {
+    for example in registry::EXAMPLES {
+        let input = example.input().unwrap_or_default();
+        for solver in registry::solvers_for(example.day, example.part) {
+            let answer = (solver.solve)(input, &Context::quiet())
+                .map(|s| s.answer)
+                .map_err(|e| e.to_string());
+            assert!(answer.as_deref() == Ok(example.answer),
+                    "{}-{} ({}) on example '{}': expected {}, got {:?}",
+                    example.day, example.part, solver.name, example.name, example.answer, answer);
+        }
+    }
+}
+
+
+#[test]
+fn examples_are_named_once_per_puzzle() {
+    for day in 1..=25 {
+        let names = puzzles::examples(day).iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[..i].contains(name),
+                    "puzzles/day{}.md has two examples named '{}'", day, name);
+        }
+    }
+}
+
+
+#[test]
+fn streaming_solvers_give_the_examples_answers() {
+    for streamer in registry::STREAMERS {
+        let examples = registry::EXAMPLES
+            .iter()
+            .filter(|e| e.day == streamer.day && e.part == streamer.part);
+        for example in examples {
+            let mut input = example.input().unwrap_or_default().as_bytes();
+            let answer = (streamer.solve)(example.name, &mut input, &Context::quiet())
+                .map(|s| s.answer)
+                .map_err(|e| e.to_string());
+            assert!(answer.as_deref() == Ok(example.answer),
+                    "{}-{} ({}, streaming) on example '{}': expected {}, got {:?}",
+                    streamer.day, streamer.part, streamer.name, example.name, example.answer,
+                    answer);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// 'aoc2020 solve <day>-<part> --explain' prints the evidence behind the answer; for 4-2 that is
+// why each passport was rejected, which should name the field and unit that were wrong.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+
+const PASSPORTS: &str = "\
+ecl:xyz pid:000000001 eyr:2025 hcl:#123abc byr:1980 iyr:2015 hgt:170cm
+
+ecl:brn pid:000000002 eyr:2025 hcl:#123abc byr:1980 iyr:2015 hgt:200cm
+
+ecl:brn pid:000000003 eyr:2025 hcl:#123abc byr:1980 iyr:2015 hgt:80in
+
+ecl:brn pid:000000004 eyr:2025 hcl:#123abc byr:1980 iyr:2015 hgt:170cm
+";
+
+
+#[test]
+fn rejected_passports_say_what_was_wrong() {
+    let dir = env::temp_dir().join(format!("aoc2020-explain-{}", std::process::id()));
+    fs::create_dir_all(dir.join("input")).unwrap();
+    fs::write(dir.join("input/day4-1.txt"), PASSPORTS).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .args(["solve", "4-2", "--explain"])
+        .current_dir(&dir)
+        .output()
+        .expect("couldn't run aoc2020");
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success(), "solve failed: {}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), vec![
+        "1",
+        "  rejected: (3)",
+        r#"    - {"passport":1,"reason":"Not valid for part: ecl - invalid eye colour: xyz"}"#,
+        r#"    - {"passport":2,"reason":"Not valid for part: hgt: height is not in range 200 cm"}"#,
+        r#"    - {"passport":3,"reason":"Not valid for part: hgt: height is not in range 80 in"}"#,
+    ]);
+}
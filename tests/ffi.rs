@@ -0,0 +1,53 @@
+// Compile tests/c/ffi_test.c against the cdylib with the system C compiler and run it.
+
+#![cfg(unix)]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+
+// The cdylib is built alongside the test binaries, in the directory above deps/.
+fn library_dir() -> PathBuf {
+    let exe = env::current_exe().expect("no path to the test binary");
+    exe.parent().and_then(|deps| deps.parent()).expect("test binary isn't in deps/").to_path_buf()
+}
+
+
+#[test]
+fn header_is_up_to_date() {
+    let header = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/include/aoc2020.h"))
+        .expect("couldn't read include/aoc2020.h");
+    assert!(header == aoc2020::ffi::header(),
+            "include/aoc2020.h is out of date; regenerate it with `aoc2020 header`");
+}
+
+
+#[test]
+fn c_program_calls_the_solvers() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let lib_dir = library_dir();
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("ffi_test");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+
+    let compiled = Command::new(&compiler)
+        .arg(format!("{}/tests/c/ffi_test.c", manifest_dir))
+        .arg(format!("-I{}/include", manifest_dir))
+        .arg(format!("-L{}", lib_dir.display()))
+        .arg("-laoc2020")
+        .arg("-o")
+        .arg(&program)
+        .output()
+        .expect("couldn't run the C compiler");
+    assert!(compiled.status.success(), "compiling ffi_test.c failed:\n{}",
+            String::from_utf8_lossy(&compiled.stderr));
+
+    let run = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &lib_dir)
+        .env("DYLD_LIBRARY_PATH", &lib_dir)
+        .output()
+        .expect("couldn't run ffi_test");
+    assert!(run.status.success(), "ffi_test failed:\n{}{}",
+            String::from_utf8_lossy(&run.stdout), String::from_utf8_lossy(&run.stderr));
+}
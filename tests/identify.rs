@@ -0,0 +1,32 @@
+// 'aoc2020 identify' should tell the days that are just lists of numbers apart by what their
+// puzzles say of the numbers, without going by the file's name.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+
+fn identify(name: &str, input_file: &str) -> String {
+    let input = fs::read_to_string(format!("{}/input/{}", env!("CARGO_MANIFEST_DIR"), input_file))
+        .expect("couldn't read the input");
+    let path = env::temp_dir().join(format!("aoc2020-{}-{}.txt", name, std::process::id()));
+    fs::write(&path, input).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .args(["identify", path.to_str().unwrap()])
+        .output()
+        .expect("couldn't run aoc2020");
+    fs::remove_file(&path).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    assert!(output.status.success(), "identify failed on {}:\n{}", input_file, stdout);
+    stdout.lines().last().unwrap_or_default().to_string()
+}
+
+
+#[test]
+fn lists_of_numbers_are_told_apart() {
+    for (input_file, day) in [("day1-1.txt", 1), ("day9.txt", 9), ("day10.txt", 10)] {
+        let verdict = identify("numbers", input_file);
+        assert!(verdict.contains(&format!("looks like the input for day {} ", day)),
+                "{} should be for day {}: {}", input_file, day, verdict);
+    }
+}
@@ -0,0 +1,30 @@
+// With the embed-inputs feature, every input the solvers are registered with should be in the
+// binary, and the same as the file it came from.
+
+#![cfg(feature = "embed-inputs")]
+
+use std::fs;
+
+use aoc2020::{inputs, registry};
+
+
+#[test]
+fn every_solver_input_is_embedded() {
+    for solver in registry::SOLVERS {
+        assert!(inputs::embedded(solver.input).is_some(),
+                "{}-{} ({}): {} isn't embedded; add it to src/inputs.rs",
+                solver.day, solver.part, solver.name, solver.input);
+    }
+}
+
+
+#[test]
+fn embedded_inputs_match_the_files() {
+    for solver in registry::SOLVERS {
+        let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"),
+                           solver.input.trim_start_matches("./"));
+        let file = fs::read_to_string(&path).expect("couldn't read the input file");
+        assert!(inputs::embedded(solver.input) == Some(file.as_str()),
+                "{} differs from its file", path);
+    }
+}
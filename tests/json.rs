@@ -0,0 +1,81 @@
+// Write JSON values and parse them back, including the awkward corners: escapes, nesting, numbers
+// too big for an f64 to hold exactly, and whatever follows the document.
+
+use aoc2020::json::{self, JsonError, Value};
+
+
+#[test]
+fn strings_round_trip_through_their_escapes() {
+    let text = "quote \" backslash \\ newline \n return \r tab \t bell \u{7} tree \u{1f384}";
+    let written = Value::String(text.to_string()).to_string();
+    let expected = concat!(r#""quote \" backslash \\ newline \n return \r tab \t"#,
+                           " bell \\u0007 tree \u{1f384}\"");
+    assert_eq!(written, expected);
+    assert_eq!(json::parse(&written).unwrap(), Value::String(text.to_string()));
+}
+
+
+#[test]
+fn unicode_escapes_are_decoded() {
+    let parsed = json::parse(r#""\u0041\u00e9\/\b\f \ud83c\udf84 \ud83c\u0041""#).unwrap();
+    // a surrogate without its other half can't be decoded, but what follows it still is
+    assert_eq!(parsed.as_str(), Some("Aé/\u{8}\u{c} \u{1f384} \u{fffd}A"));
+}
+
+
+#[test]
+fn bad_escapes_are_errors() {
+    for bad in [r#""\x""#, r#""\u00zz""#, r#""\u12""#] {
+        assert!(matches!(json::parse(bad), Err(JsonError::BadEscape(_) | JsonError::UnexpectedEnd)),
+                "expected {} not to parse", bad);
+    }
+}
+
+
+#[test]
+fn nested_values_keep_their_shape_and_key_order() {
+    let value = json::object(vec![
+        ("zeta", vec![1u64, 2, 3].into()),
+        ("alpha", json::object(vec![
+            ("empty", Value::Array(Vec::new())),
+            ("none", Option::<u32>::None.into()),
+            ("ratio", 0.25.into()),
+            ("inner", json::object(vec![("ok", true.into())])),
+        ])),
+    ]);
+    let written = value.to_string();
+    let expected = concat!(r#"{"zeta":[1,2,3],"#,
+                           r#""alpha":{"empty":[],"none":null,"ratio":0.25,"inner":{"ok":true}}}"#);
+    assert_eq!(written, expected);
+    assert_eq!(json::parse(&written).unwrap(), value);
+
+    let spaced = json::parse(" { \"a\" : [ { \"b\" : [ [ ] ] } ] } ").unwrap();
+    let b = spaced.get("a").and_then(|a| match a {
+        Value::Array(values) => values[0].get("b"),
+        _ => None,
+    });
+    assert_eq!(b, Some(&Value::Array(vec![Value::Array(Vec::new())])));
+}
+
+
+#[test]
+fn big_integers_are_exact() {
+    // 2^53 + 1 is the first whole number that an f64 can't hold
+    for n in [(1u64 << 53) + 1, u64::MAX] {
+        let parsed = json::parse(&Value::from(n).to_string()).unwrap();
+        assert_eq!(parsed.as_u64(), Some(n));
+    }
+    assert_eq!(json::parse("-7").unwrap().as_u64(), None);
+    assert_eq!(json::parse("2.5").unwrap().as_u64(), None);
+    assert_eq!(json::parse("1e3").unwrap().as_u64(), Some(1000));
+    assert_eq!(json::parse("-7").unwrap().as_f64(), Some(-7.0));
+}
+
+
+#[test]
+fn anything_after_the_document_is_an_error() {
+    assert!(matches!(json::parse("{\"a\":1} x"), Err(JsonError::UnexpectedChar('x', 8))));
+    assert!(matches!(json::parse("[1][2]"), Err(JsonError::UnexpectedChar('[', 3))));
+    assert!(matches!(json::parse("[1,2"), Err(JsonError::UnexpectedEnd)));
+    assert!(json::parse("  true \n").is_ok());
+}
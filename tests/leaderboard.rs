@@ -0,0 +1,68 @@
+// 'aoc2020 leaderboard' reads a private leaderboard's JSON export, old (ids and timestamps as
+// strings) or new (as numbers), and says what is wrong with a document it can't use.
+
+use std::env;
+use std::fs;
+use std::process::{Command, Output};
+
+
+const BOARD: &str = r#"{"event":"2020","owner_id":"1","members":{
+  "1":{"id":"1","name":"Old Style","local_score":"30","stars":"3","last_star_ts":"1607000000",
+       "completion_day_level":{
+         "1":{"1":{"get_star_ts":"1606800000"},"2":{"get_star_ts":"1606800268"}},
+         "2":{"1":{"get_star_ts":"1606900000"}}}},
+  "2":{"id":2,"name":null,"local_score":40,"stars":2,"last_star_ts":1606990000,
+       "completion_day_level":{
+         "1":{"1":{"get_star_ts":1606800000},"2":{"get_star_ts":1606803723}}}}
+}}"#;
+
+
+fn leaderboard(name: &str, document: &str) -> Output {
+    let file = env::temp_dir().join(format!("aoc2020-leaderboard-{}-{}.json", name,
+                                            std::process::id()));
+    fs::write(&file, document).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .arg("leaderboard")
+        .arg(&file)
+        .output()
+        .expect("couldn't run aoc2020");
+    fs::remove_file(&file).unwrap();
+    output
+}
+
+
+#[test]
+fn old_and_new_exports_are_read_and_ranked() {
+    let output = leaderboard("board", BOARD);
+    assert!(output.status.success(), "leaderboard failed: {}",
+            String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "Private leaderboard for 2020: 2 member(s)");
+    assert_eq!(lines[4], "  1     40      2  (anonymous user #2)  *........................");
+    assert_eq!(lines[5], "  2     30      3  Old Style            *+.......................");
+    for line in [
+        "    1  2020-12-01 05:20:00 UTC  2020-12-01 06:22:03 UTC  1h 02m 03s",
+        "    1  2020-12-01 05:20:00 UTC  2020-12-01 05:24:28 UTC  4m 28s",
+        "    2  2020-12-02 09:06:40 UTC  -                        -",
+    ] {
+        assert!(lines.contains(&line), "no {:?} in {}", line, stdout);
+    }
+}
+
+
+#[test]
+fn a_malformed_export_is_an_error() {
+    for (name, document, error) in [
+        ("truncated", r#"{"members":{"#, "unexpected end of JSON"),
+        ("no-members", r#"{"event":"2020"}"#, "no members in the leaderboard"),
+        ("bad-day", r#"{"members":{"7":{"completion_day_level":{"x":{}}}}}"#,
+         "member 7: bad day 'x'"),
+    ] {
+        let output = leaderboard(name, document);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success(), "{}: expected leaderboard to fail", name);
+        assert!(stderr.trim_end().ends_with(&format!(".json: {}", error)),
+                "{}: stderr was {:?}", name, stderr);
+    }
+}
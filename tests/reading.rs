@@ -0,0 +1,129 @@
+// Read input files into vectors, or stream them a line at a time, and say where they go wrong
+// when they do.
+
+use std::fs;
+use std::path::PathBuf;
+
+use aoc2020::utils::{self, FromBatch, InputError};
+
+
+// a file of lines in the temporary directory, named for the test that uses it
+fn input_file(name: &str, lines: &[&str]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aoc2020-{}-{}.txt", name, std::process::id()));
+    fs::write(&path, lines.join("\n")).unwrap();
+    path
+}
+
+
+#[test]
+fn read_file_parses_every_line() {
+    let path = input_file("every-line", &["1", "22", "333"]);
+    let numbers = utils::read_file::<u32>(path.to_str().unwrap());
+    fs::remove_file(&path).unwrap();
+    assert_eq!(numbers.unwrap(), vec![1, 22, 333]);
+}
+
+
+#[test]
+fn read_file_says_where_the_first_bad_line_is() {
+    let path = input_file("first-bad-line", &["1", "two", "3", "four"]);
+    let file_name = path.to_str().unwrap().to_string();
+    let numbers = utils::read_file::<u32>(&file_name);
+    fs::remove_file(&path).unwrap();
+    match numbers {
+        Err(InputError::Unparsable { path, line, text, error }) => {
+            assert_eq!((path.as_str(), line, text.as_str()), (file_name.as_str(), 2, "two"));
+            assert_eq!(error, "invalid digit found in string");
+        },
+        other => panic!("expected line 2 to be unparsable, got {:?}", other),
+    }
+}
+
+
+#[test]
+fn read_file_says_which_file_is_missing() {
+    let missing = "./input/no-such-day.txt";
+    let error = utils::read_file::<u32>(missing).unwrap_err();
+    assert!(matches!(&error, InputError::Unreadable { path, .. } if path == missing),
+            "expected {} to be unreadable, got {:?}", missing, error);
+}
+
+
+#[test]
+fn read_file_lenient_collects_every_bad_line() {
+    let path = input_file("every-bad-line", &["1", "two", "3", "four"]);
+    let read = utils::read_file_lenient::<u32>(path.to_str().unwrap());
+    fs::remove_file(&path).unwrap();
+    let (numbers, failures) = read.unwrap();
+    assert_eq!(numbers, vec![1, 3]);
+    let lines = failures
+        .iter()
+        .map(|f| match f {
+            InputError::Unparsable { line, text, .. } => (*line, text.as_str()),
+            other => panic!("expected an unparsable line, got {:?}", other),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![(2, "two"), (4, "four")]);
+}
+
+
+#[test]
+fn check_lines_checks_the_lines_that_parse_lines_parses() {
+    // a blank line in the middle is as bad as any other line that isn't a number
+    let input = "1\n\n3\nfour\n";
+    let checked = utils::check_lines::<u32>(input);
+    let bad = checked.bad.iter().map(|b| (b.line, b.text.as_str())).collect::<Vec<_>>();
+    assert_eq!((checked.checked, bad), (4, vec![(2, ""), (4, "four")]));
+    let parsed = utils::parse_lines::<u32>(input).map_err(|e| e.to_string());
+    let error = "couldn't parse input: line 2: cannot parse integer from empty string: ''";
+    assert_eq!(parsed, Err(error.to_string()));
+}
+
+
+#[test]
+fn stream_lines_parses_as_it_reads() {
+    let input = "1\r\n22\nthree\n4444";
+    let lines = utils::stream_lines::<u32, _>("in memory", input.as_bytes())
+        .map(|line| line.map_err(|e| e.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+        Ok(1),
+        Ok(22),
+        Err("in memory:3: invalid digit found in string: 'three'".to_string()),
+        Ok(4444),
+    ]);
+}
+
+
+// a batch of words, for trying out read_batches
+#[derive(Debug, PartialEq)]
+struct Words(Vec<String>);
+
+
+impl FromBatch for Words {
+    type Err = String;
+
+    fn from_batch(lines: &[&str]) -> Result<Self, Self::Err> {
+        if lines.iter().any(|l| l.contains('!')) {
+            return Err("no shouting".to_string());
+        }
+        Ok(Words(lines.iter().flat_map(|l| l.split_whitespace()).map(String::from).collect()))
+    }
+}
+
+
+#[test]
+fn read_batches_says_which_lines_each_batch_came_from() {
+    // a line of just spaces separates batches like an empty one, and extra ones don't make more
+    let input = "a b\nc\n   \nd\n\n\n\ne!\nf\n";
+    let batches = utils::read_batches::<Words>(input).collect::<Vec<_>>();
+    let words = |ws: &[&str]| Ok(Words(ws.iter().map(|w| w.to_string()).collect()));
+    assert_eq!(batches, vec![
+        (1..=2, words(&["a", "b", "c"])),
+        (4..=4, words(&["d"])),
+        (8..=9, Err("no shouting".to_string())),
+    ]);
+    assert_eq!(utils::line_range(&batches[1].0), "line 4");
+    assert_eq!(utils::line_range(&batches[2].0), "lines 8-9");
+    assert_eq!(utils::join_batch(&["a:1  b:2", " c:3 "]), "a:1 b:2 c:3");
+}
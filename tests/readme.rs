@@ -0,0 +1,59 @@
+// 'aoc2020 readme' should replace only what is between the results markers, and leave a README
+// without them (or with them the wrong way round) exactly as it was.
+
+use std::env;
+use std::fs;
+use std::process::{Command, Output};
+
+
+const START: &str = "<!-- results:start -->";
+const END: &str = "<!-- results:end -->";
+
+
+// run 'aoc2020 readme' on a README with the given contents, returning its output and the README
+// it left behind
+fn update_readme(name: &str, readme: &str) -> (Output, String) {
+    let dir = env::temp_dir().join(format!("aoc2020-readme-{}-{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("README.md");
+    fs::write(&file, readme).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .args(["readme", "--file", "README.md", "--runs", "1"])
+        .current_dir(&dir)
+        .output()
+        .expect("couldn't run aoc2020");
+    let updated = fs::read_to_string(&file).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    (output, updated)
+}
+
+
+#[test]
+fn only_the_text_between_the_markers_is_replaced() {
+    let readme = format!("# Title\n\nbefore\n{}\nold table\n{}\nafter\n", START, END);
+    let (output, updated) = update_readme("markers", &readme);
+    assert!(output.status.success(), "readme failed: {}", String::from_utf8_lossy(&output.stderr));
+    let before = format!("# Title\n\nbefore\n{}\n| Day | Title |", START);
+    assert!(updated.starts_with(&before), "the start was changed: {}", updated);
+    assert!(updated.ends_with(&format!("|\n{}\nafter\n", END)), "the end was changed: {}", updated);
+    assert!(!updated.contains("old table"));
+    assert!(updated.contains("| 1 | Report Repair |"), "no row for day 1 in {}", updated);
+}
+
+
+#[test]
+fn a_readme_without_both_markers_is_left_alone() {
+    for (name, readme) in [
+        ("no-markers", "# Title\n\nno results here\n".to_string()),
+        ("no-end", format!("# Title\n{}\nold table\n", START)),
+        ("no-start", format!("# Title\nold table\n{}\n", END)),
+        ("backwards", format!("# Title\n{}\nold table\n{}\n", END, START)),
+    ] {
+        let (output, updated) = update_readme(name, &readme);
+        assert!(!output.status.success(), "{}: expected the update to fail", name);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("README.md") && stderr.contains("results:"),
+                "{}: stderr was {:?}", name, stderr);
+        assert_eq!(updated, readme, "{}: the README was changed", name);
+    }
+}
@@ -0,0 +1,74 @@
+// Rule out answers that were rejected before: the same answer again, or one beyond an answer that
+// was too high or too low.
+
+use std::env;
+use std::fs;
+
+use aoc2020::rejected::{self, Hint, Rejected};
+
+
+fn rejected(day_part: &str, answer: &str, hint: Hint) -> Rejected {
+    let (day, part) = day_part.split_once('-').unwrap();
+    Rejected { day: day.parse().unwrap(), part: part.parse().unwrap(),
+               answer: answer.to_string(), hint }
+}
+
+
+#[test]
+fn an_answer_rejected_before_is_implausible() {
+    let record = [rejected("9-2", "35602097", Hint::Wrong), rejected("9-1", "abc", Hint::Wrong)];
+    assert_eq!(rejected::implausible(9, 2, "35602097", &record),
+               vec!["35602097 was already rejected (wrong)"]);
+    assert_eq!(rejected::implausible(9, 1, "abc", &record),
+               vec!["abc was already rejected (wrong)"]);
+    // only for the day-part it was rejected for
+    assert!(rejected::implausible(9, 1, "35602097", &record).is_empty());
+    assert!(rejected::implausible(9, 2, "35602098", &record).is_empty());
+}
+
+
+#[test]
+fn an_answer_above_one_too_high_is_implausible() {
+    let record = [rejected("9-2", "1000", Hint::TooHigh)];
+    assert_eq!(rejected::implausible(9, 2, "1001", &record),
+               vec!["1001 is higher than 1000, which was too high"]);
+    assert_eq!(rejected::implausible(9, 2, "1000", &record),
+               vec!["1000 was already rejected (too-high)"]);
+    assert!(rejected::implausible(9, 2, "999", &record).is_empty());
+    assert!(rejected::implausible(9, 2, "not a number", &record).is_empty());
+}
+
+
+#[test]
+fn an_answer_below_one_too_low_is_implausible() {
+    let record = [rejected("9-2", "1000", Hint::TooLow), rejected("9-2", "5000", Hint::TooHigh)];
+    assert_eq!(rejected::implausible(9, 2, "-3", &record),
+               vec!["-3 is lower than 1000, which was too low"]);
+    assert!(rejected::implausible(9, 2, "1001", &record).is_empty());
+    assert_eq!(rejected::flag(9, 2, "999", &record).as_deref(),
+               Some("DO NOT SUBMIT: 999 is lower than 1000, which was too low"));
+    assert_eq!(rejected::flag(9, 2, "4999", &record), None);
+}
+
+
+#[test]
+fn the_record_is_read_back_as_it_was_written() {
+    let path = env::temp_dir().join(format!("aoc2020-rejected-{}.txt", std::process::id()));
+    let file_name = path.to_str().unwrap();
+    // no record yet is nothing rejected, but a record that can't be read is an error
+    assert!(rejected::read_rejected(file_name).unwrap().is_empty());
+    assert!(rejected::read_rejected(env::temp_dir().to_str().unwrap()).is_err());
+
+    let record = [rejected("9-2", "35602097", Hint::TooLow), rejected("1-1", "42", Hint::Wrong)];
+    for r in &record {
+        rejected::record(file_name, r).unwrap();
+    }
+    let lines = fs::read_to_string(&path).unwrap();
+    let read = rejected::read_rejected(file_name);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(lines, "9-2 35602097 too-low\n1-1 42\n");
+    let read = read.unwrap();
+    assert_eq!(read.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+               vec!["9-2 35602097 too-low", "1-1 42"]);
+    assert_eq!(read[0].hint, Hint::TooLow);
+}
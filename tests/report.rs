@@ -0,0 +1,26 @@
+// 'aoc2020 report --html' puts the solvers' errors (which can quote their input) into the page, so
+// whatever the input says has to be escaped rather than written as markup.
+
+use std::env;
+use std::fs;
+use std::process::Command;
+
+
+#[test]
+fn errors_quoting_the_input_are_escaped() {
+    let dir = env::temp_dir().join(format!("aoc2020-report-{}", std::process::id()));
+    fs::create_dir_all(dir.join("input")).unwrap();
+    fs::write(dir.join("input/day8.txt"), "<td>&\"x\" +1\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .args(["report", "--html", "report.html", "--runs", "1"])
+        .current_dir(&dir)
+        .output()
+        .expect("couldn't run aoc2020");
+    let html = fs::read_to_string(dir.join("report.html"));
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(output.status.success(), "report failed: {}", String::from_utf8_lossy(&output.stderr));
+    let html = html.unwrap();
+    assert!(html.contains("invalid opcode: &lt;td&gt;&amp;&quot;x&quot;"),
+            "expected the escaped error in {}", html);
+    assert!(!html.contains("<td>&\""), "the input was written as markup");
+}
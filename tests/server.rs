@@ -0,0 +1,91 @@
+// Start 'aoc2020 serve' on a free port and check the status of the replies: the endpoints that
+// work, the ones that don't exist or are used wrongly, and requests that are too big.
+
+use std::io::prelude::*;
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+
+// the server, killed when the test is done with it (even if the test fails)
+struct Server {
+    child: Child,
+    port: u16,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
+
+
+fn start_server() -> Server {
+    // a port that was free just now; the server listens on it once this listener is dropped
+    let port = TcpListener::bind(("127.0.0.1", 0)).unwrap().local_addr().unwrap().port();
+    let child = Command::new(env!("CARGO_BIN_EXE_aoc2020"))
+        .args(["serve", "--port", &port.to_string()])
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("couldn't run aoc2020");
+    let server = Server { child, port };
+    for _ in 0..100 {
+        if TcpStream::connect(("127.0.0.1", port)).is_ok() {
+            return server;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    panic!("the server never started listening on port {}", port);
+}
+
+
+// send a raw request, returning the status code and the body of the reply
+fn send(server: &Server, request: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(("127.0.0.1", server.port)).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).unwrap();
+    let status = reply.split(' ').nth(1).and_then(|s| s.parse().ok()).expect("no status");
+    let body = reply.split_once("\r\n\r\n").map(|(_, body)| body.to_string()).unwrap_or_default();
+    (status, body)
+}
+
+
+fn post(server: &Server, path: &str, body: &str) -> (u16, String) {
+    send(server, &format!("POST {} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+                          path, body.len(), body))
+}
+
+
+#[test]
+fn requests_get_the_right_status() {
+    let server = start_server();
+    let (status, body) = send(&server, "GET /days HTTP/1.1\r\n\r\n");
+    assert_eq!(status, 200);
+    assert!(body.contains(r#""title":"Report Repair""#), "body was {}", body);
+
+    let (status, body) = post(&server, "/solve/1/1", "1721\n979\n366\n299\n675\n1456\n");
+    assert_eq!(status, 200);
+    assert!(body.contains(r#""answer":"514579""#), "body was {}", body);
+
+    assert_eq!(send(&server, "GET /nowhere HTTP/1.1\r\n\r\n").0, 404);
+    assert_eq!(post(&server, "/solve/25/1", "").0, 404);
+    assert_eq!(send(&server, "GET /solve/1/1 HTTP/1.1\r\n\r\n").0, 405);
+    assert_eq!(send(&server, "POST /days HTTP/1.1\r\n\r\n").0, 405);
+    assert_eq!(post(&server, "/solve/1/1", "not a number\n").0, 422);
+    assert_eq!(post(&server, "/solve/8/2", "acc +1\nacc +1\n").0, 422);
+    assert_eq!(send(&server, "not http\r\n\r\n").0, 400);
+}
+
+
+#[test]
+fn requests_that_are_too_big_are_refused() {
+    let server = start_server();
+    // the length is enough; the body is never read
+    let request = format!("POST /solve/1/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n", 1u64 << 40);
+    assert_eq!(send(&server, &request).0, 413);
+    let request = format!("GET /days HTTP/1.1\r\nX-Padding: {}\r\n\r\n", "x".repeat(32 * 1024));
+    assert_eq!(send(&server, &request).0, 431);
+}
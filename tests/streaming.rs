@@ -0,0 +1,31 @@
+// Run every streaming solver on the input of its day-part's primary solver: it should give the
+// verified answer (input/answers.txt), and the same answer as the solver that reads it all at once.
+
+use aoc2020::context::Context;
+use aoc2020::answers::{self, ANSWERS_FILE};
+use aoc2020::{inputs, registry};
+
+
+#[test]
+fn streaming_solvers_agree_with_the_solvers_on_the_inputs() {
+    let verified = answers::read_expected(ANSWERS_FILE).unwrap();
+    assert!(!verified.is_empty(), "no verified answers in {}", ANSWERS_FILE);
+    for streamer in registry::STREAMERS {
+        let solver = registry::solvers_for(streamer.day, streamer.part)[0];
+        let input = inputs::read(solver.input).expect("couldn't read a solver's input");
+        let streamed = (streamer.solve)(solver.input, &mut input.as_bytes(), &Context::quiet())
+            .map(|s| s.answer)
+            .map_err(|e| e.to_string());
+        let solved = (solver.solve)(&input, &Context::quiet())
+            .map(|s| s.answer)
+            .map_err(|e| e.to_string());
+        assert!(streamed.is_ok() && streamed == solved,
+                "{}-{} ({}, streaming) on {}: got {:?}, but {} got {:?}", streamer.day,
+                streamer.part, streamer.name, solver.input, streamed, solver.name, solved);
+        if let Some(expected) = answers::expected_for(&verified, streamer.day, streamer.part) {
+            assert_eq!(streamed.as_deref(), Ok(expected.answer.as_str()),
+                       "{}-{} ({}, streaming) on {}", streamer.day, streamer.part, streamer.name,
+                       solver.input);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// Run every day-part's walkthrough with its output captured, and check that it said what it
+// reported, and that the one answer it reported for the input is the verified one
+// (input/answers.txt), as that is what 'aoc2020 <day>-<part>' checks against rejected answers.
+
+use aoc2020::answers::{self, ANSWERS_FILE};
+use aoc2020::context::{Context, Sink};
+use aoc2020::progress::Progress;
+use aoc2020::registry;
+
+
+#[test]
+fn walkthroughs_report_the_verified_answers() {
+    let verified = answers::read_expected(ANSWERS_FILE).unwrap();
+    assert!(!verified.is_empty(), "no verified answers in {}", ANSWERS_FILE);
+    for walkthrough in registry::WALKTHROUGHS {
+        let ctx = Context::new(Sink::Capture, Progress::hidden());
+        if let Err(e) = (walkthrough.run)(&ctx) {
+            panic!("{}-{} failed: {}", walkthrough.day, walkthrough.part, e);
+        }
+        let output = ctx.captured();
+        let reported = ctx.answers();
+        for (label, value) in &reported {
+            let line = format!("{}: {}", label, value.to_text());
+            assert!(output.lines().any(|l| l == line),
+                    "{}-{} reported '{}' without saying so", walkthrough.day, walkthrough.part,
+                    line);
+        }
+        let expected = answers::expected_for(&verified, walkthrough.day, walkthrough.part);
+        let for_input = ctx.input_answers()
+            .iter()
+            .map(|(_, value)| value.to_text())
+            .collect::<Vec<_>>();
+        match expected {
+            Some(expected) => assert!(for_input == [expected.answer.as_str()],
+                                      "{}-{} didn't report {} for the input; it reported {:?}",
+                                      walkthrough.day, walkthrough.part, expected.answer,
+                                      reported),
+            None => assert_eq!(for_input.len(), 1, "{}-{} reported {:?}",
+                               walkthrough.day, walkthrough.part, reported),
+        }
+    }
+}